@@ -1254,6 +1254,14 @@ fn main() {
             .collect::<Vec<_>>()
     );
 
+    // errors of the operations wrapped in manual.rs that libvips doesn't introspect here,
+    // each group listed after the generated operation it follows
+    let manual_errors = vec![
+        ("GifloadSource", vec!["Gifsave", "GifsaveBuffer", "GifsaveTarget"]),
+        ("JpegloadBuffer", vec!["Jxlload", "JxlloadBuffer", "JxlloadSource"]),
+        ("JpegsaveMime", vec!["Jxlsave", "JxlsaveBuffer", "JxlsaveTarget"]),
+    ];
+
     let (methods, errors, errors_display) = operations
         .iter()
         .filter(|o| !operation_blacklist.contains(&o.vips_operation.as_str()))
@@ -1261,15 +1269,21 @@ fn main() {
             (String::new(), String::new(), String::new()),
             |(mut methods, mut errors, mut errors_display), operation| {
                 methods.push_str(operation.body().as_str());
-                errors.push_str(format!("{}Error,\n", operation.name.to_class_case()).as_str());
-                errors_display.push_str(
-                    format!(
-                        "Error::{}Error => write!(f, \"vips error: {}Error. Check error buffer for more details\"),\n",
-                        operation.name.to_class_case(),
-                        operation.name.to_class_case()
-                    )
-                    .as_str(),
-                );
+                let mut names = vec![operation.name.to_class_case()];
+                if let Some((_, manual)) = manual_errors.iter().find(|(after, _)| *after == names[0]) {
+                    names.extend(manual.iter().map(|name| name.to_string()));
+                }
+                for name in names {
+                    errors.push_str(format!("{}Error,\n", name).as_str());
+                    errors_display.push_str(
+                        format!(
+                            "Error::{}Error => write!(f, \"vips error: {}Error. Check error buffer for more details\"),\n",
+                            name,
+                            name
+                        )
+                        .as_str(),
+                    );
+                }
                 (methods, errors, errors_display)
             },
         );
@@ -1318,6 +1332,7 @@ fn main() {
         InitializationError(&'static str),
         OperationError(&'static str),
         IOError(&'static str),
+        UnsupportedFormat(&'static str),
         LinearError,
         CaseError,
         GetpointError,
@@ -1330,6 +1345,7 @@ fn main() {
                 Error::InitializationError(msg) => write!(f, "vips error: InitializationError - {{}}", msg),
                 Error::OperationError(msg) => write!(f, "vips error: OperationError - {{}}", msg),
                 Error::IOError(msg) => write!(f, "vips error: IOError - {{}}", msg),
+                Error::UnsupportedFormat(format) => write!(f, "vips error: UnsupportedFormat - {{}} is not supported by this libvips build", format),
                 Error::LinearError => write!(f, "vips error: LinearError. Check error buffer for more details"),
                 Error::CaseError => write!(f, "vips error: CaseError. Check error buffer for more details"),
                 Error::GetpointError => write!(f, "vips error: GetpointError. Check error buffer for more details"),
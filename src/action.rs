@@ -1,29 +1,769 @@
 use crate::VipsImage;
+use crate::VipsInterpolate;
+use crate::error::Error;
 use crate::Result;
 use crate::bindings;
+use crate::exif::Exif;
 use crate::ops;
 use crate::ops::image_get_string;
 
 use std::cmp;
 use std::collections::HashMap;
-use std::ffi::{CString};
+use std::ffi::{c_void, CStr, CString};
 
-pub fn format(buf: &[u8], format_type: &str) -> Result<Vec<u8>> {
+/// Options shared by the actions that decode, transform and re-encode an image.
+/// Every such action has a `_with_opts` variant taking these; the plain one uses the defaults
+#[derive(Clone, Debug)]
+pub struct ActionOptions {
+    /// auto_orient: `bool` -> Rotate the image upright from its EXIF orientation and reset the tag
+    /// default: true
+    pub auto_orient: bool,
+    /// encode: `EncodeOptions` -> How the result is encoded
+    pub encode: EncodeOptions,
+    /// page: `i32` -> Page of a PDF, TIFF or HEIF document the actions work on, counting from 0
+    /// default: 0
+    pub page: i32,
+    /// normalise_profile: `bool` -> Convert resized images with a wide-gamut RGB or a CMYK profile to sRGB.
+    /// sRGB and grey profiles are always kept as they are
+    /// default: true
+    pub normalise_profile: bool,
+}
+
+impl std::default::Default for ActionOptions {
+    fn default() -> Self {
+        ActionOptions {
+            auto_orient: true,
+            encode: EncodeOptions::default(),
+            page: 0,
+            normalise_profile: true,
+        }
+    }
+}
+
+// the defaults of the actions that didn't rotate the image before `auto_orient` was added
+fn unrotated_options() -> ActionOptions {
+    ActionOptions {
+        auto_orient: false,
+        ..ActionOptions::default()
+    }
+}
+
+/// Encoder settings. Each one is passed to the savers that have it (jpegsave, pngsave, webpsave, tiffsave, heifsave, gifsave,
+/// jxlsave)
+/// and left out for the others
+#[derive(Clone, Debug)]
+pub struct EncodeOptions {
+    /// quality: `Option<i32>` -> `Q` of jpeg, webp, heif, avif, jxl and tiff (with jpeg or webp compression),
+    /// and of png with `palette`.
+    /// `None` keeps the saver's default
+    /// min: 1, max: 100, default: None
+    pub quality: Option<i32>,
+    /// progressive: `bool` -> `interlace` of jpeg and png
+    /// default: false
+    pub progressive: bool,
+    /// optimize_coding: `bool` -> Compute optimal Huffman tables for jpeg
+    /// default: false
+    pub optimize_coding: bool,
+    /// subsample: `ForeignJpegSubsample` -> Chroma subsampling of jpeg, heif and avif
+    ///  `Auto` -> subsample below quality 90 [DEFAULT]
+    ///  `On` -> always subsample
+    ///  `Off` -> never subsample
+    pub subsample: ops::ForeignJpegSubsample,
+    /// compression: `i32` -> zlib compression level of png
+    /// min: 0, max: 9, default: 6
+    pub compression: i32,
+    /// palette: `bool` -> Quantise png to an 8-bit palette
+    /// default: false
+    pub palette: bool,
+    /// lossless: `bool` -> Lossless webp, heif, avif and jxl
+    /// default: false
+    pub lossless: bool,
+    /// effort: `i32` -> `reduction_effort` of webp, higher is smaller and slower
+    /// min: 0, max: 6, default: 4
+    pub effort: i32,
+    /// heif_effort: `Option<i32>` -> Encoding effort of heif and avif, higher is smaller and slower.
+    /// Older libvips only have the reverse `speed`, which is set to 9 - effort. `None` keeps the saver's default
+    /// min: 0, max: 9, default: None
+    pub heif_effort: Option<i32>,
+    /// tiff_compression: `ForeignTiffCompression` -> Compression of tiff
+    /// default: None
+    pub tiff_compression: ops::ForeignTiffCompression,
+    /// dither: `f64` -> Amount of dithering when gif is quantised
+    /// min: 0, max: 1, default: 1
+    pub dither: f64,
+    /// bitdepth: `i32` -> Bits per pixel of gif (1 to 8, at most 2^bitdepth colours),
+    /// or bits per channel of heif and avif (8, 10 or 12, other than 8 from libvips 8.13). Other formats ignore it
+    /// default: 8
+    pub bitdepth: i32,
+    /// interframe_maxerror: `f64` -> Pixels of a gif frame closer than this to the previous frame are left transparent
+    /// min: 0, max: 32, default: 0
+    pub interframe_maxerror: f64,
+    /// strip: `bool` -> Leave out all metadata
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for EncodeOptions {
+    fn default() -> Self {
+        EncodeOptions {
+            quality: None,
+            progressive: false,
+            optimize_coding: false,
+            subsample: ops::ForeignJpegSubsample::Auto,
+            compression: 6,
+            palette: false,
+            lossless: false,
+            effort: 4,
+            heif_effort: None,
+            tiff_compression: ops::ForeignTiffCompression::None,
+            dither: 1.0,
+            bitdepth: 8,
+            interframe_maxerror: 0.0,
+            strip: false,
+        }
+    }
+}
+
+impl EncodeOptions {
+    // checks the options that apply when saving as `_type`. Options the format doesn't have aren't checked
+    fn validate(&self, _type: &str) -> Result<()> {
+        if let Some(quality) = self.quality {
+            if !(1..=100).contains(&quality) {
+                return Err(Error::OperationError("Quality must be between 1 and 100"))
+            }
+        }
+        if !(0..=9).contains(&self.compression) {
+            return Err(Error::OperationError("Compression level must be between 0 and 9"))
+        }
+        if !(0..=6).contains(&self.effort) {
+            return Err(Error::OperationError("Effort must be between 0 and 6"))
+        }
+        if let Some(effort) = self.heif_effort {
+            if !(0..=9).contains(&effort) {
+                return Err(Error::OperationError("HEIF effort must be between 0 and 9"))
+            }
+        }
+        match _type {
+            "gif" => {
+                if !(1..=8).contains(&self.bitdepth) {
+                    return Err(Error::OperationError("GIF bit depth must be between 1 and 8"))
+                }
+                if !(0.0..=1.0).contains(&self.dither) {
+                    return Err(Error::OperationError("Dither must be between 0 and 1"))
+                }
+                if !(0.0..=32.0).contains(&self.interframe_maxerror) {
+                    return Err(Error::OperationError("Inter-frame error must be between 0 and 32"))
+                }
+            }
+            "heif" | "avif" if ![8, 10, 12].contains(&self.bitdepth) => {
+                return Err(Error::OperationError("HEIF bit depth must be 8, 10 or 12"))
+            }
+            "heif" | "avif" if self.bitdepth != 8 && !vips_version_at_least(8, 13) => {
+                return Err(Error::OperationError("HEIF bit depths other than 8 need libvips 8.13"))
+            }
+            _ => (),
+        }
+        return Ok(())
+    }
+
+    fn jpegsave_options(&self, profile: &str, page_height: i32) -> ops::JpegsaveBufferOptions {
+        ops::JpegsaveBufferOptions {
+            q: self.quality.unwrap_or(75),
+            profile: profile.to_string(),
+            optimize_coding: self.optimize_coding,
+            interlace: self.progressive,
+            subsample_mode: self.subsample,
+            strip: self.strip,
+            background: vec![0.0],
+            page_height,
+            ..ops::JpegsaveBufferOptions::default()
+        }
+    }
+
+    fn pngsave_options(&self, profile: &str, page_height: i32, bitdepth: i32) -> ops::PngsaveBufferOptions {
+        ops::PngsaveBufferOptions {
+            compression: self.compression,
+            interlace: self.progressive,
+            profile: profile.to_string(),
+            palette: self.palette,
+            q: if self.palette { self.quality.unwrap_or(100) } else { 100 },
+            bitdepth,
+            strip: self.strip,
+            background: vec![0.0],
+            page_height,
+            ..ops::PngsaveBufferOptions::default()
+        }
+    }
+
+    fn webpsave_options(&self, profile: &str, page_height: i32) -> ops::WebpsaveBufferOptions {
+        ops::WebpsaveBufferOptions {
+            q: self.quality.unwrap_or(75),
+            lossless: self.lossless,
+            reduction_effort: self.effort,
+            profile: profile.to_string(),
+            strip: self.strip,
+            background: vec![0.0],
+            page_height,
+            ..ops::WebpsaveBufferOptions::default()
+        }
+    }
+
+    fn tiffsave_options(&self, profile: &str, page_height: i32, image: &VipsImage) -> ops::TiffsaveBufferOptions {
+        // tiffsave only writes the image's own resolution when xres and yres aren't passed
+        let resunit = match image.get_string("resolution-unit") {
+            Ok(ref unit) if unit == "in" => ops::ForeignTiffResunit::Inch,
+            _ => ops::ForeignTiffResunit::Cm,
+        };
+        ops::TiffsaveBufferOptions {
+            compression: self.tiff_compression,
+            q: self.quality.unwrap_or(75),
+            profile: profile.to_string(),
+            resunit,
+            xres: image.get_xres(),
+            yres: image.get_yres(),
+            strip: self.strip,
+            background: vec![0.0],
+            page_height,
+            ..ops::TiffsaveBufferOptions::default()
+        }
+    }
+
+    /// the `[...]` option string for saving as `_type`, empty when every option is at its default.
+    /// Used for the formats and images `save` can't hand to a typed saver
+    pub(crate) fn option_string(&self, _type: &str) -> String {
+        let mut options: Vec<String> = Vec::new();
+        let quality = self.quality.map(|quality| format!("Q={}", quality));
+        match _type {
+            "jpeg" | "jpg" => {
+                options.extend(quality);
+                if self.progressive {
+                    options.push(String::from("interlace"));
+                }
+                if self.optimize_coding {
+                    options.push(String::from("optimize_coding"));
+                }
+                // older libvips only has the `no_subsample` switch
+                match self.subsample {
+                    ops::ForeignJpegSubsample::On if vips_version_at_least(8, 10) => {
+                        options.push(String::from("subsample_mode=on"))
+                    }
+                    ops::ForeignJpegSubsample::Off if vips_version_at_least(8, 10) => {
+                        options.push(String::from("subsample_mode=off"))
+                    }
+                    ops::ForeignJpegSubsample::Off => options.push(String::from("no_subsample")),
+                    _ => (),
+                }
+            }
+            "png" => {
+                if self.compression != 6 {
+                    options.push(format!("compression={}", self.compression));
+                }
+                if self.progressive {
+                    options.push(String::from("interlace"));
+                }
+                if self.palette {
+                    options.push(String::from("palette"));
+                    options.extend(quality);
+                }
+            }
+            "webp" => {
+                options.extend(quality);
+                if self.lossless {
+                    options.push(String::from("lossless"));
+                }
+                if self.effort != 4 {
+                    options.push(format!("reduction_effort={}", self.effort));
+                }
+            }
+            "tiff" => {
+                let compression = match self.tiff_compression {
+                    ops::ForeignTiffCompression::Jpeg => Some("jpeg"),
+                    ops::ForeignTiffCompression::Deflate => Some("deflate"),
+                    ops::ForeignTiffCompression::Packbit => Some("packbits"),
+                    ops::ForeignTiffCompression::Ccittfax4 => Some("ccittfax4"),
+                    ops::ForeignTiffCompression::Lzw => Some("lzw"),
+                    ops::ForeignTiffCompression::Webp => Some("webp"),
+                    ops::ForeignTiffCompression::Zstd => Some("zstd"),
+                    _ => None,
+                };
+                if let Some(compression) = compression {
+                    options.push(format!("compression={}", compression));
+                }
+                options.extend(quality);
+            }
+            "heif" | "avif" => {
+                // heifsave writes HEVC unless told otherwise, even into a .avif
+                if _type == "avif" {
+                    options.push(String::from("compression=av1"));
+                }
+                options.extend(quality);
+                if self.lossless {
+                    options.push(String::from("lossless"));
+                }
+                if let Some(effort) = self.heif_effort {
+                    if vips_version_at_least(8, 13) {
+                        options.push(format!("effort={}", effort));
+                    } else {
+                        options.push(format!("speed={}", 9 - effort));
+                    }
+                }
+                // `validate` rejects other bit depths before 8.13
+                if self.bitdepth != 8 && vips_version_at_least(8, 13) {
+                    options.push(format!("bitdepth={}", self.bitdepth));
+                }
+                // heifsave subsamples by quality alone before 8.13
+                if vips_version_at_least(8, 13) {
+                    match self.subsample {
+                        ops::ForeignJpegSubsample::On => options.push(String::from("subsample_mode=on")),
+                        ops::ForeignJpegSubsample::Off => options.push(String::from("subsample_mode=off")),
+                        _ => (),
+                    }
+                }
+            }
+            "jxl" => {
+                options.extend(quality);
+                if self.lossless {
+                    options.push(String::from("lossless"));
+                }
+            }
+            _ => (),
+        }
+        if self.strip {
+            options.push(String::from("strip"));
+        }
+        if options.is_empty() {
+            return String::new()
+        }
+        return format!("[{}]", options.join(","))
+    }
+}
+
+/// decodes `buf`. Animated GIF and WebP load with every frame, stacked vertically with `page-height` set,
+/// documents load the page in `options.page`
+pub(crate) fn load(buf: &[u8], options: &ActionOptions) -> Result<VipsImage> {
+    let option_string = load_option_string(&vips_image_type(buf), options)?;
+    return load_with_option_string(buf, &option_string, options)
+}
+
+// the loader options for an input of type `_type`
+pub(crate) fn load_option_string(_type: &str, options: &ActionOptions) -> Result<String> {
+    if options.page < 0 {
+        return Err(Error::OperationError("Page must not be negative"))
+    }
+    let option_string = match _type {
+        "gif" | "webp" => String::from("n=-1"),
+        "pdf" | "tiff" | "heif" | "avif" => format!("page={}", options.page),
+        _ => String::new(),
+    };
+    return Ok(option_string)
+}
+
+/// decodes the first frame of `buf` only, for images that are used as a whole like watermark overlays
+pub(crate) fn load_first_frame(buf: &[u8], options: &ActionOptions) -> Result<VipsImage> {
+    return load_with_option_string(buf, "", options)
+}
+
+fn load_with_option_string(buf: &[u8], option_string: &str, options: &ActionOptions) -> Result<VipsImage> {
+    let image = VipsImage::new_from_buffer(buf, option_string)?;
+    if options.auto_orient {
+        return auto_orient(&image)
+    }
+    return Ok(image)
+}
+
+/// rotates the image upright with `ops::autorot` and resets the orientation, so viewers don't rotate it again
+pub(crate) fn auto_orient(image: &VipsImage) -> Result<VipsImage> {
+    let rotated = map_frames(image, ops::autorot)?;
+    let mut image = ops::copy(&rotated)?;
+    image.set_int("orientation", 1)?;
+    image.remove_field("exif-ifd0-Orientation")?;
+    return Ok(image)
+}
+
+/// height of one frame: `page-height` for animated and multi-page images, the full height otherwise
+pub(crate) fn frame_height(image: &VipsImage) -> i32 {
+    // libvips falls back to the image height when page-height is missing or doesn't divide it
+    return image.get_page_height()
+}
+
+/// applies `f` to every frame of an animated or multi-page image and stacks the results again,
+/// or applies it to the whole image when there is a single frame. All frames must come out the same size
+pub(crate) fn map_frames<F>(image: &VipsImage, f: F) -> Result<VipsImage>
+where
+    F: Fn(&VipsImage) -> Result<VipsImage>,
+{
+    let page_height = frame_height(image);
+    let n_pages = image.get_height() / page_height;
+    if n_pages <= 1 {
+        return f(image)
+    }
+    let mut frames = Vec::with_capacity(n_pages as usize);
+    for page in 0..n_pages {
+        let frame = ops::extract_area(image, 0, page * page_height, image.get_width(), page_height)?;
+        // a stale page-height could make the processed frame look like several pages
+        let mut frame = ops::copy(&frame)?;
+        frame.remove_field("page-height")?;
+        frames.push(f(&frame)?);
+    }
+    let frame_height = frames[0].get_height();
+    if frames.iter().any(|frame| frame.get_height() != frame_height || frame.get_width() != frames[0].get_width()) {
+        return Err(Error::OperationError("Frames of an animation must all be the same size"))
+    }
+    // arrayjoin puts every image in one row unless told otherwise, and keeps the header of the first frame,
+    // so delays and loop count carry over
+    let arrayjoin_options = ops::ArrayjoinOptions {
+        across: 1,
+        background: vec![0.0],
+        hspacing: frames[0].get_width(),
+        vspacing: frame_height,
+        ..ops::ArrayjoinOptions::default()
+    };
+    let joined = ops::arrayjoin_with_opts(&mut frames, &arrayjoin_options)?;
+    let mut joined = ops::copy(&joined)?;
+    joined.set_int("page-height", frame_height)?;
+    return Ok(joined)
+}
+
+/// Image formats known to the actions. Which ones work depends on how libvips was built, see `supported_formats`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Jpeg,
+    Png,
+    Webp,
+    Tiff,
+    Gif,
+    /// load only
+    Pdf,
+    /// load only
+    Svg,
+    Heif,
+    Avif,
+    Jxl,
+    /// load only, through ImageMagick
+    Bmp,
+    /// load only, through ImageMagick
+    Ico,
+    /// load only, through ImageMagick
+    Psd,
+}
+
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 13] = [
+        ImageFormat::Jpeg,
+        ImageFormat::Png,
+        ImageFormat::Webp,
+        ImageFormat::Tiff,
+        ImageFormat::Gif,
+        ImageFormat::Pdf,
+        ImageFormat::Svg,
+        ImageFormat::Heif,
+        ImageFormat::Avif,
+        ImageFormat::Jxl,
+        ImageFormat::Bmp,
+        ImageFormat::Ico,
+        ImageFormat::Psd,
+    ];
+
+    /// the lowercase name, as used for the save suffix and in `Metadata::ttype`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Tiff => "tiff",
+            ImageFormat::Gif => "gif",
+            ImageFormat::Pdf => "pdf",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Heif => "heif",
+            ImageFormat::Avif => "avif",
+            ImageFormat::Jxl => "jxl",
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Ico => "ico",
+            ImageFormat::Psd => "psd",
+        }
+    }
+
+    /// parses a format name or a common file extension, like `jpg` or `heic`, ignoring case
+    pub fn from_name(name: &str) -> Option<ImageFormat> {
+        let format = match name.trim_start_matches('.').to_lowercase().as_str() {
+            "jpeg" | "jpg" | "jpe" => ImageFormat::Jpeg,
+            "png" => ImageFormat::Png,
+            "webp" => ImageFormat::Webp,
+            "tiff" | "tif" => ImageFormat::Tiff,
+            "gif" => ImageFormat::Gif,
+            "pdf" => ImageFormat::Pdf,
+            "svg" => ImageFormat::Svg,
+            "heif" | "heic" => ImageFormat::Heif,
+            "avif" => ImageFormat::Avif,
+            "jxl" => ImageFormat::Jxl,
+            "bmp" => ImageFormat::Bmp,
+            "ico" => ImageFormat::Ico,
+            "psd" => ImageFormat::Psd,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// whether this libvips build has a loader for the format
+    pub fn can_load(&self) -> bool {
+        return is_type_supported(&self.name().to_uppercase())
+    }
+
+    /// whether this libvips build can encode the format to a buffer. For AVIF this encodes a single pixel
+    /// the first time, since libheif can be built without an AV1 encoder
+    pub fn can_save(&self) -> bool {
+        let saver = match self {
+            ImageFormat::Jpeg => "jpegsave_buffer",
+            ImageFormat::Png => "pngsave_buffer",
+            ImageFormat::Webp => "webpsave_buffer",
+            ImageFormat::Tiff => "tiffsave_buffer",
+            ImageFormat::Gif if gifsave_available() => "gifsave_buffer",
+            ImageFormat::Gif => "magicksave_buffer",
+            ImageFormat::Heif => "heifsave_buffer",
+            ImageFormat::Avif => return *AV1_ENCODER,
+            ImageFormat::Jxl => "jxlsave_buffer",
+            ImageFormat::Pdf | ImageFormat::Svg | ImageFormat::Bmp | ImageFormat::Ico | ImageFormat::Psd => {
+                return false
+            }
+        };
+        return vips_type_find("VipsOperation", saver) != 0
+    }
+}
+
+/// What the running libvips can do with a format
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FormatSupport {
+    pub format: ImageFormat,
+    pub load: bool,
+    pub save: bool,
+}
+
+/// reports, for every `ImageFormat`, whether the loaded libvips can decode and encode it
+pub fn supported_formats() -> Vec<FormatSupport> {
+    return ImageFormat::ALL
+        .iter()
+        .map(|format| FormatSupport {
+            format: *format,
+            load: format.can_load(),
+            save: format.can_save(),
+        })
+        .collect()
+}
+
+/// re-encodes the image as `format_type`. Returns `Error::UnsupportedFormat` if libvips can't write it
+pub fn format(buf: &[u8], format_type: ImageFormat) -> Result<Vec<u8>> {
+    return format_with_opts(buf, format_type, &unrotated_options())
+}
+
+pub fn format_with_opts(buf: &[u8], format_type: ImageFormat, options: &ActionOptions) -> Result<Vec<u8>> {
+    if !format_type.can_save() {
+        return Err(Error::UnsupportedFormat(format_type.name()))
+    }
+    let image = load(buf, options)?;
+    return save(&image, format_type.name(), &options.encode)
+}
+
+/// number of pages in a PDF, TIFF or HEIF document, or frames in an animation. 1 for other images
+pub fn page_count(buf: &[u8]) -> Result<i32> {
     let image = VipsImage::new_from_buffer(buf, "")?;
-    return image.image_write_to_buffer(&format!(".{}", format_type))
+    return Ok(image.get_n_pages())
+}
+
+/// encodes page `page` of a document as `format_type`, counting from 0. Like `format`, the page isn't rotated
+pub fn page(buf: &[u8], page: i32, format_type: ImageFormat) -> Result<Vec<u8>> {
+    return page_with_opts(buf, page, format_type, &unrotated_options())
+}
+
+/// `page` takes the place of `options.page`
+pub fn page_with_opts(buf: &[u8], page: i32, format_type: ImageFormat, options: &ActionOptions) -> Result<Vec<u8>> {
+    let options = ActionOptions {
+        page,
+        ..options.clone()
+    };
+    return format_with_opts(buf, format_type, &options)
+}
+
+/// renders page `page` of a PDF at `dpi` and encodes it as `format_type`
+pub fn render_pdf(buf: &[u8], page: i32, dpi: f64, format_type: ImageFormat) -> Result<Vec<u8>> {
+    let pdfload_options = ops::PdfloadBufferOptions {
+        page,
+        dpi,
+        ..ops::PdfloadBufferOptions::default()
+    };
+    return render_pdf_with_opts(buf, format_type, &pdfload_options, &ActionOptions::default())
+}
+
+/// renders a PDF with `ops::pdfload_buffer_with_opts`. With `n` above 1 the pages are stacked vertically,
+/// and only formats with frames keep them apart
+pub fn render_pdf_with_opts(
+    buf: &[u8],
+    format_type: ImageFormat,
+    pdfload_options: &ops::PdfloadBufferOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
+    if detect_format(buf) != Some(ImageFormat::Pdf) {
+        return Err(Error::OperationError("Input is not a PDF"))
+    }
+    if !ImageFormat::Pdf.can_load() {
+        return Err(Error::UnsupportedFormat(ImageFormat::Pdf.name()))
+    }
+    if !format_type.can_save() {
+        return Err(Error::UnsupportedFormat(format_type.name()))
+    }
+    let image = ops::pdfload_buffer_with_opts(buf, pdfload_options)?;
+    return save(&image, format_type.name(), &options.encode)
+}
+
+/// Layout of `contact_sheet`
+#[derive(Clone, Debug)]
+pub struct ContactSheetOptions {
+    /// across: `i32` -> Number of pages in each row
+    /// default: 4
+    pub across: i32,
+    /// tile_size: `i32` -> Every page is shrunk to fit a square of this size
+    /// default: 256
+    pub tile_size: i32,
+    /// spacing: `i32` -> Pixels between pages
+    /// default: 0
+    pub spacing: i32,
+    /// background: `Vec<f64>` -> Colour around and behind the pages
+    /// default: [255.0]
+    pub background: Vec<f64>,
+}
+
+impl std::default::Default for ContactSheetOptions {
+    fn default() -> Self {
+        ContactSheetOptions {
+            across: 4,
+            tile_size: 256,
+            spacing: 0,
+            background: vec![255.0],
+        }
+    }
+}
+
+/// renders every page of a document, or every frame of an animation, into one grid image
+pub fn contact_sheet(buf: &[u8], format_type: ImageFormat) -> Result<Vec<u8>> {
+    return contact_sheet_with_opts(buf, format_type, &ContactSheetOptions::default(), &ActionOptions::default())
 }
 
-pub fn crop(buf: &[u8], width: i32, height: i32, gravity: i32) -> Result<Vec<u8>> {
+/// renders every page into one grid image. `options.page` is ignored
+pub fn contact_sheet_with_opts(
+    buf: &[u8],
+    format_type: ImageFormat,
+    contact_sheet_options: &ContactSheetOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
+    if !format_type.can_save() {
+        return Err(Error::UnsupportedFormat(format_type.name()))
+    }
+    if contact_sheet_options.across < 1 || contact_sheet_options.tile_size < 1 || contact_sheet_options.spacing < 0 {
+        return Err(Error::OperationError("Contact sheet layout must be positive"))
+    }
+    let tile_size = contact_sheet_options.tile_size + contact_sheet_options.spacing;
+    // only the loaders of paged formats take a page option
+    let paged = matches!(vips_image_type(buf).as_str(), "gif" | "webp" | "pdf" | "tiff" | "heif" | "avif");
+    let mut tiles = Vec::new();
+    for page in 0..page_count(buf)? {
+        // pages are loaded one by one, a document can mix page sizes and a stacked load can't
+        let thumbnail_options = ops::ThumbnailBufferOptions2 {
+            height: contact_sheet_options.tile_size,
+            no_rotate: !options.auto_orient,
+            option_string: if paged { format!("page={}", page) } else { String::new() },
+            ..ops::ThumbnailBufferOptions2::default()
+        };
+        let tile = ops::thumbnail_buffer_with_opts2(buf, contact_sheet_options.tile_size, &thumbnail_options)?;
+        // arrayjoin needs the same bands everywhere, so every page becomes opaque sRGB
+        let mut tile = ops::colourspace(&tile, ops::Interpretation::Srgb)?;
+        if tile.image_hasalpha() {
+            let flatten_options = ops::FlattenOptions {
+                background: contact_sheet_options.background.clone(),
+                ..ops::FlattenOptions::default()
+            };
+            tile = ops::flatten_with_opts(&tile, &flatten_options)?;
+        }
+        let embed_options = ops::EmbedOptions {
+            extend: ops::Extend::Background,
+            background: contact_sheet_options.background.clone(),
+        };
+        let (left, top) = calculate_crop(tile_size, tile_size, tile.get_width(), tile.get_height(), Gravity::Centre);
+        tiles.push(ops::embed_with_opts(&tile, left, top, tile_size, tile_size, &embed_options)?);
+    }
+    // every tile has the same size, so setting the spacing avoids the defaults of arrayjoin_with_opts
+    let arrayjoin_options = ops::ArrayjoinOptions {
+        across: cmp::min(contact_sheet_options.across, tiles.len() as i32),
+        background: contact_sheet_options.background.clone(),
+        hspacing: tile_size,
+        vspacing: tile_size,
+        ..ops::ArrayjoinOptions::default()
+    };
+    let image = ops::arrayjoin_with_opts(&mut tiles, &arrayjoin_options)?;
+    return save(&image, format_type.name(), &options.encode)
+}
+
+/// Where `crop` anchors the area it keeps
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gravity {
+    Centre,
+    North,
+    East,
+    South,
+    West,
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+    /// keep the area with the most detail (`ops::Interesting::Entropy`)
+    Entropy,
+    /// keep the area most likely to draw the eye, like faces and skin tones (`ops::Interesting::Attention`)
+    Attention,
+}
+
+pub fn crop(buf: &[u8], width: i32, height: i32, gravity: Gravity) -> Result<Vec<u8>> {
+    return crop_with_opts(buf, width, height, gravity, &ActionOptions::default())
+}
+
+pub fn crop_with_opts(
+    buf: &[u8],
+    width: i32,
+    height: i32,
+    gravity: Gravity,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
     let _type = vips_image_type(buf);
-    let input = VipsImage::new_from_buffer(buf, "")?;
+    let input = load(buf, options)?;
+    let image = crop_image(&input, width, height, gravity)?;
+    return save(&image, &_type, &options.encode)
+}
 
+pub(crate) fn crop_image(
+    input: &VipsImage,
+    width: i32,
+    height: i32,
+    gravity: Gravity,
+) -> Result<VipsImage> {
     let _width: i32 = input.get_width();
-    let _height: i32 = input.get_height();
+    let _height: i32 = frame_height(input);
     let width_in: i32 = cmp::min(_width, width);
     let height_in: i32 = cmp::min(_height, height);
-    let (left, top) = calculate_crop(_width, _height, width, height, gravity);
-    let image = ops::extract_area(&input, left, top, width_in, height_in)?; 
-    return image.image_write_to_buffer(&format!(".{}", _type))
+    let interesting = match gravity {
+        Gravity::Entropy => ops::Interesting::Entropy,
+        Gravity::Attention => ops::Interesting::Attention,
+        _ => {
+            let (left, top) = calculate_crop(_width, _height, width_in, height_in, gravity);
+            return map_frames(input, |frame| ops::extract_area(frame, left, top, width_in, height_in))
+        }
+    };
+    let smartcrop_options = ops::SmartcropOptions { interesting };
+    if _height == input.get_height() {
+        return ops::smartcrop_with_opts(input, width_in, height_in, &smartcrop_options)
+    }
+    // the window is picked on the first frame only, so the frames of an animation don't jump around
+    let first_frame = ops::extract_area(input, 0, 0, _width, _height)?;
+    let cropped = ops::smartcrop_with_opts(&first_frame, width_in, height_in, &smartcrop_options)?;
+    // smartcrop ends with extract_area, which records the window as negative offsets
+    let (left, top) = (-cropped.get_xoffset(), -cropped.get_yoffset());
+    return map_frames(input, |frame| ops::extract_area(frame, left, top, width_in, height_in))
 }
 
 #[derive(Debug)]
@@ -51,22 +791,24 @@ pub fn get_metadata(buf: &[u8]) -> Result<Metadata> {
     let ttype = vips_image_type(buf);
     let image = VipsImage::new_from_buffer(buf, "")?;
 
-    let orientation = image_get_string(&image, "exif-ifd0-Orientation");
-    let make = image_get_string(&image, "exif-ifd0-Make");
-    let mode = image_get_string(&image, "exif-ifd0-Model");
-    let datetime = image_get_string(&image, "exif-ifd0-DateTime");
-    let exif_version = image_get_string(&image, "exif-ifd2-ExifVersion");
-    let focal_length = image_get_string(&image, "exif-ifd2-FocalLength");
-    let gps_latitude_ref = image_get_string(&image, "exif-ifd3-GPSLatitudeRef");
-    let gps_latitude = image_get_string(&image, "exif-ifd3-GPSLatitude");
-    let gps_longitude_ref = image_get_string(&image, "exif-ifd3-GPSLongitudeRef");
-    let gps_longitude = image_get_string(&image, "exif-ifd3-GPSLongitude");
-    let gps_altitude_ref = image_get_string(&image, "exif-ifd3-GPSAltitudeRef");
-    let gps_altitude = image_get_string(&image, "exif-ifd3-GPSAltitude");
+    // fields missing from the header (e.g. no EXIF in a PNG) are reported as empty strings
+    let field = |name: &str| image_get_string(&image, name);
+    let orientation = field("exif-ifd0-Orientation");
+    let make = field("exif-ifd0-Make");
+    let mode = field("exif-ifd0-Model");
+    let datetime = field("exif-ifd0-DateTime");
+    let exif_version = field("exif-ifd2-ExifVersion");
+    let focal_length = field("exif-ifd2-FocalLength");
+    let gps_latitude_ref = field("exif-ifd3-GPSLatitudeRef");
+    let gps_latitude = field("exif-ifd3-GPSLatitude");
+    let gps_longitude_ref = field("exif-ifd3-GPSLongitudeRef");
+    let gps_longitude = field("exif-ifd3-GPSLongitude");
+    let gps_altitude_ref = field("exif-ifd3-GPSAltitudeRef");
+    let gps_altitude = field("exif-ifd3-GPSAltitude");
     let width = image.get_width();
     let height = image.get_height();
-    let alpha = false;
-    let space = String::from("");
+    let alpha = image.image_hasalpha();
+    let space = format!("{:?}", image.get_interpretation()?).to_lowercase();
     let metadata = Metadata {
         width,
         height,
@@ -90,75 +832,995 @@ pub fn get_metadata(buf: &[u8]) -> Result<Metadata> {
     return Ok(metadata);
 }
 
+pub fn get_exif(buf: &[u8]) -> Result<Exif> {
+    let image = VipsImage::new_from_buffer(buf, "")?;
+    return Ok(Exif::from_image(&image))
+}
+
+/// Which metadata `strip_metadata` keeps. Everything else (EXIF, GPS, XMP, IPTC, comments) is removed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StripPolicy {
+    /// keep only the ICC colour profile
+    KeepIcc,
+    /// keep the ICC colour profile and the orientation tag
+    KeepOrientationAndIcc,
+    /// keep the ICC colour profile and the EXIF copyright notice
+    KeepCopyright,
+    /// remove all metadata, including the colour profile
+    StripAll,
+}
+
+impl StripPolicy {
+    fn keeps(&self, field: &str) -> bool {
+        match self {
+            StripPolicy::KeepIcc => field == "icc-profile-data",
+            StripPolicy::KeepOrientationAndIcc => {
+                field == "icc-profile-data" || field == "orientation" || field == "exif-ifd0-Orientation"
+            }
+            StripPolicy::KeepCopyright => field == "icc-profile-data" || field == "exif-ifd0-Copyright",
+            StripPolicy::StripAll => false,
+        }
+    }
+}
+
+fn is_metadata_field(field: &str) -> bool {
+    field.starts_with("exif-")
+        || field.starts_with("png-comment-")
+        || field == "xmp-data"
+        || field == "iptc-data"
+        || field == "icc-profile-data"
+        || field == "orientation"
+        || field == "gif-comment"
+        || field == "jpeg-thumbnail-data"
+}
+
+pub fn strip_metadata(buf: &[u8], policy: StripPolicy) -> Result<Vec<u8>> {
+    return strip_metadata_with_opts(buf, policy, &unrotated_options())
+}
+
+pub fn strip_metadata_with_opts(buf: &[u8], policy: StripPolicy, options: &ActionOptions) -> Result<Vec<u8>> {
+    let _type = vips_image_type(buf);
+    let input = load(buf, options)?;
+    let image = strip_image(&input, policy)?;
+    let encode_options = EncodeOptions {
+        strip: options.encode.strip || policy == StripPolicy::StripAll,
+        ..options.encode.clone()
+    };
+    return save(&image, &_type, &encode_options)
+}
+
+/// removes the metadata fields `policy` doesn't keep. `StripAll` also needs the `strip` save option,
+/// which drops what libvips would otherwise write on its own
+pub(crate) fn strip_image(input: &VipsImage, policy: StripPolicy) -> Result<VipsImage> {
+    // the loaded image may be shared through the vips cache, so edit the header of a copy
+    let mut image = ops::copy(input)?;
+    // the raw exif-data blob is always dropped: on save libvips rebuilds the EXIF block from
+    // the exif-ifd* fields that are left, so removed tags can't leak back in through it
+    for field in image.get_fields() {
+        if is_metadata_field(&field) && !policy.keeps(&field) {
+            image.remove_field(&field)?;
+        }
+    }
+    return Ok(image)
+}
+
+/// returns the embedded ICC profile, or `None` if the image has none
+pub fn get_icc_profile(buf: &[u8]) -> Result<Option<Vec<u8>>> {
+    let image = VipsImage::new_from_buffer(buf, "")?;
+    if image.get_typeof("icc-profile-data")?.is_none() {
+        return Ok(None)
+    }
+    return image.get_blob("icc-profile-data").map(Some)
+}
+
+/// attaches `profile` as the embedded ICC profile without touching the pixels.
+/// Use it to tag images whose pixels are already in the profile's colour space
+pub fn embed_icc_profile(buf: &[u8], profile: &[u8]) -> Result<Vec<u8>> {
+    return embed_icc_profile_with_opts(buf, profile, &unrotated_options())
+}
+
+pub fn embed_icc_profile_with_opts(buf: &[u8], profile: &[u8], options: &ActionOptions) -> Result<Vec<u8>> {
+    let _type = vips_image_type(buf);
+    let input = load(buf, options)?;
+    let mut image = ops::copy(&input)?;
+    image.set_blob("icc-profile-data", profile)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// converts the pixels to sRGB using the embedded profile (or a default one for the image's colour space)
+/// and embeds the sRGB profile
+pub fn icc_to_srgb(buf: &[u8], intent: ops::Intent) -> Result<Vec<u8>> {
+    return icc_convert(buf, "srgb", intent)
+}
+
+pub fn icc_to_srgb_with_opts(buf: &[u8], intent: ops::Intent, options: &ActionOptions) -> Result<Vec<u8>> {
+    return icc_convert_with_opts(buf, "srgb", intent, options)
+}
+
+/// converts the pixels to `output_profile` and embeds it. `output_profile` is a filename
+/// or one of the profiles built into libvips, like `srgb` or `cmyk`
+pub fn icc_convert(buf: &[u8], output_profile: &str, intent: ops::Intent) -> Result<Vec<u8>> {
+    return icc_convert_with_opts(buf, output_profile, intent, &unrotated_options())
+}
+
+pub fn icc_convert_with_opts(
+    buf: &[u8],
+    output_profile: &str,
+    intent: ops::Intent,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
+    let _type = vips_image_type(buf);
+    let input = load(buf, options)?;
+    let image = icc_transform(&input, output_profile, intent)?;
+    return save(&image, &_type, &options.encode)
+}
+
+pub(crate) fn icc_transform(
+    image: &VipsImage,
+    output_profile: &str,
+    intent: ops::Intent,
+) -> Result<VipsImage> {
+    // the embedded profile wins; the input profile only covers images that don't have one
+    let input_profile = match image.get_interpretation()? {
+        ops::Interpretation::Cmyk => "cmyk",
+        _ => "srgb",
+    };
+    // 16-bit images would otherwise come back with 8 bits per channel
+    let depth = match image.get_format()? {
+        ops::BandFormat::Ushort => 16,
+        _ => 8,
+    };
+    let options = ops::IccTransformOptions {
+        intent,
+        embedded: true,
+        input_profile: input_profile.to_string(),
+        depth,
+        ..ops::IccTransformOptions::default()
+    };
+    return ops::icc_transform_with_opts(image, output_profile, &options)
+}
+
+/// How `resize_with_opts` fits the image into the requested width and height
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Fit {
+    /// scale to fit inside the box, keeping the aspect ratio
+    Inside,
+    /// scale to cover the box, keeping the aspect ratio. The output can be larger than the box
+    Outside,
+    /// scale to cover the box, keeping the aspect ratio, then crop what overflows using the gravity
+    Cover,
+    /// scale to fit inside the box, keeping the aspect ratio, then pad it to the exact size with the background
+    Contain,
+    /// scale to the box exactly, ignoring the aspect ratio
+    Fill,
+}
+
+/// Options for `resize_with_opts`
+#[derive(Clone, Debug)]
+pub struct ResizeOptions {
+    /// fit: `Fit` -> How the image is fitted into the box
+    /// default: Inside
+    pub fit: Fit,
+    /// gravity: `Gravity` -> Which part of the image `Fit::Cover` keeps
+    /// default: Centre
+    pub gravity: Gravity,
+    /// background: `Vec<f64>` -> Colour of the padding added by `Fit::Contain`. One value per band, or a single value for all bands
+    /// default: [0.0], black or fully transparent when the image has an alpha band
+    pub background: Vec<f64>,
+    /// without_enlargement: `bool` -> Never scale the image up. With `Fit::Contain` a small image is padded instead
+    /// default: false
+    pub without_enlargement: bool,
+}
+
+impl std::default::Default for ResizeOptions {
+    fn default() -> Self {
+        ResizeOptions {
+            fit: Fit::Inside,
+            gravity: Gravity::Centre,
+            background: vec![0.0],
+            without_enlargement: false,
+        }
+    }
+}
+
+/// scales the image to fit inside `width` x `height`. A `height` of 0 means the same as `width`
 pub fn resize(buf: &[u8], width: i32, height: i32) -> Result<Vec<u8>> {
+    return resize_with_opts(buf, width, height, &ResizeOptions::default(), &ActionOptions::default())
+}
+
+/// scales the image into `width` x `height` as `resize_options.fit` says. A `height` of 0 means the same as `width`
+pub fn resize_with_opts(
+    buf: &[u8],
+    width: i32,
+    height: i32,
+    resize_options: &ResizeOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
+    let _type = vips_image_type(buf);
+    let image = thumbnail_fit(buf, &_type, width, height, resize_options, options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// scales both sides by `scale` with the given resampling kernel
+pub fn resize_scale(buf: &[u8], scale: f64, kernel: ops::Kernel) -> Result<Vec<u8>> {
+    return resize_scale_with_opts(buf, scale, kernel, &ActionOptions::default())
+}
+
+pub fn resize_scale_with_opts(
+    buf: &[u8],
+    scale: f64,
+    kernel: ops::Kernel,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(Error::OperationError("Resize scale must be a positive number"))
+    }
+    let _type = vips_image_type(buf);
+    let input = load(buf, options)?;
+    let resize_options = ops::ResizeOptions {
+        kernel,
+        ..ops::ResizeOptions::default()
+    };
+    let image = map_frames(&input, |frame| ops::resize_with_opts(frame, scale, &resize_options))?;
+    let image = normalise_profile(image, options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// shrinks the image so its longest side is at most `max_edge` pixels. Smaller images are only re-encoded
+pub fn resize_longest_edge(buf: &[u8], max_edge: i32) -> Result<Vec<u8>> {
+    return resize_longest_edge_with_opts(buf, max_edge, &ActionOptions::default())
+}
+
+pub fn resize_longest_edge_with_opts(buf: &[u8], max_edge: i32, options: &ActionOptions) -> Result<Vec<u8>> {
+    let _type = vips_image_type(buf);
+    let resize_options = ResizeOptions {
+        without_enlargement: true,
+        ..ResizeOptions::default()
+    };
+    let image = thumbnail_fit(buf, &_type, max_edge, max_edge, &resize_options, options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// shrinks the image, keeping the aspect ratio, so it has at most `max_megapixels` million pixels.
+/// Smaller images are only re-encoded
+pub fn resize_max_pixels(buf: &[u8], max_megapixels: f64) -> Result<Vec<u8>> {
+    return resize_max_pixels_with_opts(buf, max_megapixels, &ActionOptions::default())
+}
+
+pub fn resize_max_pixels_with_opts(buf: &[u8], max_megapixels: f64, options: &ActionOptions) -> Result<Vec<u8>> {
+    if !max_megapixels.is_finite() || max_megapixels <= 0.0 {
+        return Err(Error::OperationError("Pixel limit must be a positive number"))
+    }
     let _type = vips_image_type(buf);
-    let image = ops::thumbnail_buffer_with_opts1(buf, width, height, &_type)?;
+    let (input_width, input_height) = header_dimensions(buf, &_type, options)?;
+    let (width, height) = max_pixels_dimensions(input_width, input_height, max_megapixels * 1_000_000.0);
+    // fill with both sides rounded down keeps the aspect ratio and stays under the limit
+    let resize_options = ResizeOptions {
+        fit: Fit::Fill,
+        without_enlargement: true,
+        ..ResizeOptions::default()
+    };
+    let image = thumbnail_fit(buf, &_type, width, height, &resize_options, options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+// width and height of one frame as `load` would hand it back, read from the header alone: unlike `load`,
+// this doesn't build the autorot pipeline, so orientations 5 to 8 swap the sides here instead
+fn header_dimensions(buf: &[u8], _type: &str, options: &ActionOptions) -> Result<(i32, i32)> {
+    let header = VipsImage::new_from_buffer(buf, &load_option_string(_type, options)?)?;
+    let (width, height) = (header.get_width(), frame_height(&header));
+    if options.auto_orient && (5..=8).contains(&header.get_int("orientation").unwrap_or(1)) {
+        return Ok((height, width))
+    }
+    return Ok((width, height))
+}
+
+/// the largest size with the aspect ratio of `width` x `height` that has at most `max_pixels` pixels
+pub(crate) fn max_pixels_dimensions(width: i32, height: i32, max_pixels: f64) -> (i32, i32) {
+    let pixels = f64::from(width) * f64::from(height);
+    if pixels <= max_pixels {
+        return (width, height)
+    }
+    let scale = (max_pixels / pixels).sqrt();
+    let scaled = |size: i32| cmp::max(1, (f64::from(size) * scale).floor() as i32);
+    return (scaled(width), scaled(height))
+}
+
+/// decodes and shrinks `buf` with `ops::thumbnail_buffer_with_opts2`, then crops or pads it as the fit needs
+pub(crate) fn thumbnail_fit(
+    buf: &[u8],
+    _type: &str,
+    width: i32,
+    height: i32,
+    resize_options: &ResizeOptions,
+    options: &ActionOptions,
+) -> Result<VipsImage> {
+    let height = if height == 0 { width } else { height };
+    if width <= 0 || height < 0 {
+        return Err(Error::OperationError("Resize dimensions must be positive"))
+    }
+    // thumbnail applies the EXIF orientation itself and drops the tag
+    let mut thumbnail_options = ops::ThumbnailBufferOptions2 {
+        height,
+        no_rotate: !options.auto_orient,
+        option_string: load_option_string(_type, options)?,
+        ..ops::ThumbnailBufferOptions2::default()
+    };
+    let mut thumbnail_width = width;
+    if resize_options.fit == Fit::Inside || resize_options.fit == Fit::Contain {
+        if resize_options.without_enlargement {
+            thumbnail_options.size = ops::Size::Down;
+        }
+    } else {
+        let (input_width, input_height) = header_dimensions(buf, _type, options)?;
+        let (fit_width, fit_height) = fit_dimensions(input_width, input_height, width, height, resize_options);
+        thumbnail_width = fit_width;
+        thumbnail_options.height = fit_height;
+        thumbnail_options.size = ops::Size::Force;
+    }
+    // thumbnail sizes each frame of an animation, with the height applying to one frame
+    let image = ops::thumbnail_buffer_with_opts2(buf, thumbnail_width, &thumbnail_options)?;
+    let image = fit_box(&image, width, height, resize_options)?;
+    return normalise_profile(image, options)
+}
+
+/// scales an image that is already decoded into `width` x `height` as `resize_options.fit` says.
+/// A `height` of 0 means the same as `width`
+pub(crate) fn resize_image(
+    input: &VipsImage,
+    width: i32,
+    height: i32,
+    resize_options: &ResizeOptions,
+    options: &ActionOptions,
+) -> Result<VipsImage> {
+    let height = if height == 0 { width } else { height };
+    if width <= 0 || height < 0 {
+        return Err(Error::OperationError("Resize dimensions must be positive"))
+    }
+    let image = map_frames(input, |frame| resize_frame(frame, width, height, resize_options))?;
+    let image = fit_box(&image, width, height, resize_options)?;
+    return normalise_profile(image, options)
+}
+
+// scales one frame to the size `fit_box` then crops or pads
+fn resize_frame(input: &VipsImage, width: i32, height: i32, resize_options: &ResizeOptions) -> Result<VipsImage> {
+    let (input_width, input_height) = (input.get_width(), input.get_height());
+    let (fit_width, fit_height) = fit_dimensions(input_width, input_height, width, height, resize_options);
+    let resize_options_in = ops::ResizeOptions {
+        vscale: f64::from(fit_height) / f64::from(input_height),
+        ..ops::ResizeOptions::default()
+    };
+    return ops::resize_with_opts(input, f64::from(fit_width) / f64::from(input_width), &resize_options_in)
+}
+
+/// the size an `input_width` x `input_height` image is scaled to before `fit_box` crops or pads it
+pub(crate) fn fit_dimensions(
+    input_width: i32,
+    input_height: i32,
+    width: i32,
+    height: i32,
+    resize_options: &ResizeOptions,
+) -> (i32, i32) {
+    let mut hscale = f64::from(width) / f64::from(input_width);
+    let mut vscale = f64::from(height) / f64::from(input_height);
+    match resize_options.fit {
+        Fit::Inside | Fit::Contain => {
+            hscale = f64::min(hscale, vscale);
+            vscale = hscale;
+        }
+        Fit::Outside | Fit::Cover => {
+            hscale = f64::max(hscale, vscale);
+            vscale = hscale;
+        }
+        Fit::Fill => (),
+    }
+    if resize_options.without_enlargement {
+        hscale = f64::min(hscale, 1.0);
+        vscale = f64::min(vscale, 1.0);
+    }
+    let scaled = |size: i32, scale: f64| cmp::max(1, (f64::from(size) * scale).round() as i32);
+    (scaled(input_width, hscale), scaled(input_height, vscale))
+}
+
+// crops (cover) or pads (contain) every frame of a scaled image to exactly `width` x `height`.
+// Cover crops all frames in one go, so a smart crop keeps the same window for the whole animation
+fn fit_box(image: &VipsImage, width: i32, height: i32, resize_options: &ResizeOptions) -> Result<VipsImage> {
+    match resize_options.fit {
+        Fit::Cover => crop_image(image, width, height, resize_options.gravity),
+        Fit::Contain => map_frames(image, |frame| {
+            let embed_options = ops::EmbedOptions {
+                extend: ops::Extend::Background,
+                background: resize_options.background.clone(),
+            };
+            let width = cmp::max(width, frame.get_width());
+            let height = cmp::max(height, frame.get_height());
+            let (left, top) = calculate_crop(width, height, frame.get_width(), frame.get_height(), Gravity::Centre);
+            ops::embed_with_opts(frame, left, top, width, height, &embed_options)
+        }),
+        _ => ops::copy(image),
+    }
+}
+
+// wide-gamut sources (e.g. Display P3 phone photos) are normalised so they render the same everywhere
+fn normalise_profile(image: VipsImage, options: &ActionOptions) -> Result<VipsImage> {
+    if !options.normalise_profile || image.get_typeof("icc-profile-data")?.is_none() {
+        return Ok(image)
+    }
+    if needs_srgb_conversion(&image.get_blob("icc-profile-data")?) {
+        return icc_transform(&image, "srgb", ops::Intent::Perceptual)
+    }
+    return Ok(image)
+}
+
+// CMYK and RGB profiles other than sRGB; grey and sRGB images gain nothing from a transform
+pub(crate) fn needs_srgb_conversion(profile: &[u8]) -> bool {
+    // bytes 16..20 of the ICC header name the colour space of the data
+    match profile.get(16..20) {
+        Some(b"CMYK") => true,
+        Some(b"RGB ") => !is_srgb_profile(profile),
+        _ => false,
+    }
+}
+
+// the sRGB profile built into libvips, matched byte for byte or by its profile ID
+fn is_srgb_profile(profile: &[u8]) -> bool {
+    SRGB_PROFILE.as_deref().is_some_and(|srgb| {
+        profile == srgb || icc_profile_id(srgb).is_some_and(|id| icc_profile_id(profile) == Some(id))
+    })
+}
+
+// bytes 84..100 of the ICC header, an MD5 of the profile that is left as zeros when it wasn't computed
+fn icc_profile_id(profile: &[u8]) -> Option<&[u8]> {
+    profile.get(84..100).filter(|id| id.iter().any(|byte| *byte != 0))
+}
+
+// encodes to `_type`. gif goes through `ops::gifsave_buffer` and only falls back to ImageMagick
+// with a libvips that is too old to write gif itself. jpeg, png, webp and tiff use their typed savers;
+// heif, avif and jxl, whose options in these bindings lack effort, bitdepth or Q, take the option string
+pub(crate) fn save(image: &VipsImage, _type: &str, encode_options: &EncodeOptions) -> Result<Vec<u8>> {
+    encode_options.validate(_type)?;
     if _type == "gif" {
-        return image.image_write_to_magicksave_buffer(); 
+        if !gifsave_available() {
+            return image.image_write_to_magicksave_buffer();
+        }
+        let gifsave_options = ops::GifsaveBufferOptions {
+            dither: encode_options.dither,
+            bitdepth: encode_options.bitdepth,
+            interframe_maxerror: encode_options.interframe_maxerror,
+            strip: encode_options.strip,
+            ..ops::GifsaveBufferOptions::default()
+        };
+        return ops::gifsave_buffer_with_opts(image, &gifsave_options)
+    }
+    // formats without frames would write every frame of an animation as one tall image
+    let page_height = frame_height(image);
+    let first_frame;
+    let image = if page_height < image.get_height() && !is_multi_frame_format(_type) {
+        first_frame = ops::extract_area(image, 0, 0, image.get_width(), page_height)?;
+        &first_frame
+    } else {
+        image
+    };
+    match (_type, saver_profile(image, encode_options.strip)?) {
+        ("jpeg", Some(profile)) | ("jpg", Some(profile)) => {
+            ops::jpegsave_buffer_with_opts(image, &encode_options.jpegsave_options(profile, page_height))
+        }
+        ("png", Some(profile)) => {
+            let bitdepth = match image.get_format()? {
+                ops::BandFormat::Ushort => 16,
+                _ => 8,
+            };
+            ops::pngsave_buffer_with_opts(image, &encode_options.pngsave_options(profile, page_height, bitdepth))
+        }
+        ("webp", Some(profile)) => {
+            ops::webpsave_buffer_with_opts(image, &encode_options.webpsave_options(profile, page_height))
+        }
+        ("tiff", Some(profile)) => {
+            ops::tiffsave_buffer_with_opts(image, &encode_options.tiffsave_options(profile, page_height, image))
+        }
+        _ => image.image_write_to_buffer(&format!(".{}{}", _type, encode_options.option_string(_type))),
     }
-    return image.image_write_to_buffer(&format!(".{}", _type))
 }
 
+// the `profile` for the typed savers, which always pass it and so replace whatever profile the image carries.
+// "none" and libvips' built-in "sRGB" can be named there; an image holding any other profile gets `None`
+// and is saved through the option string, which keeps it
+fn saver_profile(image: &VipsImage, strip: bool) -> Result<Option<&'static str>> {
+    if strip || image.get_typeof("icc-profile-data")?.is_none() {
+        return Ok(Some("none"))
+    }
+    if is_srgb_profile(&image.get_blob("icc-profile-data")?) {
+        return Ok(Some("sRGB"))
+    }
+    return Ok(None)
+}
+
+fn is_multi_frame_format(_type: &str) -> bool {
+    matches!(_type, "gif" | "webp" | "tiff" | "heif" | "avif")
+}
+
+/// Options for `rotate_with_opts`. They only apply to angles that aren't a multiple of 90°,
+/// which are rotated losslessly
+#[derive(Clone, Debug)]
+pub struct RotateOptions {
+    /// background: `Vec<f64>` -> Colour of the corners uncovered by the rotation. One value per band, or a single value for all bands
+    /// default: [0.0], black or fully transparent when the image has an alpha band
+    pub background: Vec<f64>,
+    /// interpolate: `String` -> Nickname of the interpolator, e.g. `nearest`, `bilinear`, `bicubic`, `nohalo`
+    /// default: "bicubic"
+    pub interpolate: String,
+}
+
+impl std::default::Default for RotateOptions {
+    fn default() -> Self {
+        RotateOptions {
+            background: vec![0.0],
+            interpolate: String::from("bicubic"),
+        }
+    }
+}
+
+/// rotates clockwise by `angle` degrees
 pub fn rotate(buf: &[u8], angle: i32) -> Result<Vec<u8>> {
+    return rotate_with_opts(
+        buf,
+        f64::from(angle),
+        &RotateOptions::default(),
+        &ActionOptions::default(),
+    )
+}
+
+/// rotates clockwise by `angle` degrees. Multiples of 90° use the lossless `ops::rot`,
+/// any other angle goes through `ops::rotate_with_opts` and grows the canvas to fit
+pub fn rotate_with_opts(
+    buf: &[u8],
+    angle: f64,
+    rotate_options: &RotateOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
     let _type = vips_image_type(buf);
-    let input = VipsImage::new_from_buffer(buf, "")?;
-    let angle_in = match angle {
-        90_i32 => ops::Angle::D90,  // Angle 90
-        180_i32 => ops::Angle::D180, // Angle 180
-        270_i32 => ops::Angle::D270, // Angle 270
-        _ => ops::Angle::D0,       // Angle 0
+    let input = load(buf, options)?;
+    let image = rotate_image(&input, angle, rotate_options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+pub(crate) fn rotate_image(
+    input: &VipsImage,
+    angle: f64,
+    rotate_options: &RotateOptions,
+) -> Result<VipsImage> {
+    return map_frames(input, |frame| rotate_frame(frame, angle, rotate_options))
+}
+
+fn rotate_frame(input: &VipsImage, angle: f64, rotate_options: &RotateOptions) -> Result<VipsImage> {
+    if !angle.is_finite() {
+        return Err(Error::OperationError("Rotation angle must be a finite number"))
+    }
+    if rotate_options.background.is_empty() {
+        return Err(Error::OperationError("Rotation background needs at least one value"))
+    }
+    let angle = angle.rem_euclid(360.0);
+    if angle % 90.0 == 0.0 {
+        let angle_in = match angle as i32 {
+            90_i32 => ops::Angle::D90,  // Angle 90
+            180_i32 => ops::Angle::D180, // Angle 180
+            270_i32 => ops::Angle::D270, // Angle 270
+            _ => ops::Angle::D0,       // Angle 0
+        };
+        return ops::rot(input, angle_in)
+    }
+    // all fields are set explicitly: the default interpolator is a static one that must not be dropped
+    let rotate_options = ops::RotateOptions {
+        interpolate: VipsInterpolate::new_from_name(&rotate_options.interpolate)?,
+        background: rotate_options.background.clone(),
+        odx: 0.0,
+        ody: 0.0,
+        idx: 0.0,
+        idy: 0.0,
+    };
+    return ops::rotate_with_opts(input, angle, &rotate_options)
+}
+
+/// Placement and blending of a watermark overlay
+#[derive(Clone, Debug)]
+pub struct WatermarkOptions {
+    /// gravity: `Gravity` -> Where the overlay is anchored on the base image. `Entropy` and `Attention` anchor at the centre
+    /// default: NorthWest
+    pub gravity: Gravity,
+    /// x: `i32` -> Horizontal distance in pixels from the edge the overlay is anchored to. Positive values move it inwards;
+    /// for the centred gravities they move it right
+    /// default: 0
+    pub x: i32,
+    /// y: `i32` -> Vertical distance in pixels from the edge the overlay is anchored to. Positive values move it inwards;
+    /// for the centred gravities they move it down
+    /// default: 0
+    pub y: i32,
+    /// scale: `f64` -> Overlay width as a fraction of the base image width. 0 keeps the overlay at its own size
+    /// min: 0, default: 0
+    pub scale: f64,
+    /// opacity: `f64` -> Multiplies the overlay alpha
+    /// min: 0, max: 1, default: 1
+    pub opacity: f64,
+    /// mode: `WatermarkMode` -> Whether the overlay is placed once or repeated over the whole image
+    /// default: Single
+    pub mode: WatermarkMode,
+}
+
+impl std::default::Default for WatermarkOptions {
+    fn default() -> Self {
+        WatermarkOptions {
+            gravity: Gravity::NorthWest,
+            x: 0,
+            y: 0,
+            scale: 0.0,
+            opacity: 1.0,
+            mode: WatermarkMode::Single,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WatermarkMode {
+    /// one overlay, placed by `gravity`, `x` and `y`
+    Single,
+    /// the overlay rotated by `angle` degrees and repeated across the whole image with `spacing` pixels
+    /// between copies. `gravity`, `x` and `y` are ignored
+    Tiled { angle: f64, spacing: i32 },
+}
+
+/// `opacity` goes from 0 to 1 and `color` is a `#RRGGBB` or `#RGB` hex colour
+pub fn watermark_text(buf: &[u8], text: &str, dpi: i32, opacity: f32, color: &str) -> Result<Vec<u8>> {
+    let watermark_options = WatermarkOptions {
+        opacity: f64::from(opacity),
+        ..WatermarkOptions::default()
     };
-    let image = ops::rot(&input, angle_in)?;
-    return image.image_write_to_buffer(&format!(".{}", _type))
+    return watermark_text_with_opts(buf, text, dpi, color, &watermark_options, &ActionOptions::default())
 }
 
-pub fn watermark_text(buf: &[u8], text: &str, dpi: i32, _opacity: f32, color: &str) -> Result<Vec<u8>> {
+pub fn watermark_text_with_opts(
+    buf: &[u8],
+    text: &str,
+    dpi: i32,
+    color: &str,
+    watermark_options: &WatermarkOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
     let _type = vips_image_type(buf);
-    let overlay = ops::text_with_opts1(text, dpi, color)?;
-    let input = VipsImage::new_from_buffer(buf, "")?;
-    let image = ops::composite_2(&input, &overlay, ops::BlendMode::Over)?;
-    return image.image_write_to_buffer(&format!(".{}", _type))
+    let overlay = text_overlay(text, dpi, color)?;
+    let input = load(buf, options)?;
+    let image = composite_watermark(&input, &overlay, watermark_options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// `x` and `y` are offsets from the top-left corner and `opacity` goes from 0 to 100
+pub fn watermark_image(buf: &[u8], overlay: &[u8], x: i32, y: i32, opacity: i32) -> Result<Vec<u8>> {
+    let watermark_options = WatermarkOptions {
+        x,
+        y,
+        opacity: f64::from(opacity) / 100.0,
+        ..WatermarkOptions::default()
+    };
+    return watermark_image_with_opts(buf, overlay, &watermark_options, &ActionOptions::default())
 }
 
-pub fn watermark_image(buf: &[u8], overlay: &[u8], _x: i32, _y: i32, _opacity: i32) -> Result<Vec<u8>> {
+pub fn watermark_image_with_opts(
+    buf: &[u8],
+    overlay: &[u8],
+    watermark_options: &WatermarkOptions,
+    options: &ActionOptions,
+) -> Result<Vec<u8>> {
     let _type = vips_image_type(buf);
-    let overlay_in = VipsImage::new_from_buffer(overlay, "")?;
-    let input = VipsImage::new_from_buffer(buf, "")?;
-    let image = ops::composite_2(&input, &overlay_in, ops::BlendMode::Over)?;
-    return image.image_write_to_buffer(&format!(".{}", _type))
+    let overlay_in = load_first_frame(overlay, options)?;
+    let input = load(buf, options)?;
+    let image = composite_watermark(&input, &overlay_in, watermark_options)?;
+    return save(&image, &_type, &options.encode)
+}
+
+/// renders `text` as an sRGB image in `color`, with the glyph coverage as alpha
+pub(crate) fn text_overlay(text: &str, dpi: i32, color: &str) -> Result<VipsImage> {
+    let text_style = ops::TextStyle {
+        dpi,
+        color: color.to_string(),
+        rgba: true,
+        ..ops::TextStyle::default()
+    };
+    return ops::text_with_style(text, &text_style)
+}
+
+/// scales, fades and places `overlay` on `base` according to `watermark_options`
+pub(crate) fn composite_watermark(
+    base: &VipsImage,
+    overlay: &VipsImage,
+    watermark_options: &WatermarkOptions,
+) -> Result<VipsImage> {
+    if !(0.0..=1.0).contains(&watermark_options.opacity) {
+        return Err(Error::OperationError("Watermark opacity must be between 0 and 1"))
+    }
+    if !watermark_options.scale.is_finite() || watermark_options.scale < 0.0 {
+        return Err(Error::OperationError("Watermark scale must be zero or a positive number"))
+    }
+    let mut overlay = ops::colourspace(overlay, ops::Interpretation::Srgb)?;
+    if watermark_options.scale > 0.0 {
+        let target_width = f64::from(base.get_width()) * watermark_options.scale;
+        overlay = ops::resize(&overlay, target_width / f64::from(overlay.get_width()))?;
+    }
+    if !overlay.image_hasalpha() {
+        overlay = ops::bandjoin_const(&overlay, &mut [255.0])?;
+    }
+    if watermark_options.opacity < 1.0 {
+        let bands = overlay.get_bands() as usize;
+        let mut a = vec![1.0; bands];
+        a[bands - 1] = watermark_options.opacity;
+        let mut b = vec![0.0; bands];
+        overlay = ops::linear_with_opts(&overlay, &mut a, &mut b, &ops::LinearOptions { uchar: true })?;
+    }
+
+    if let WatermarkMode::Tiled { angle, spacing } = watermark_options.mode {
+        overlay = tile_overlay(&overlay, base.get_width(), frame_height(base), angle, spacing)?;
+    }
+    // every frame of an animation gets the same overlay
+    return map_frames(base, |frame| {
+        let (x, y) = match watermark_options.mode {
+            WatermarkMode::Single => watermark_position(frame, &overlay, watermark_options),
+            WatermarkMode::Tiled { .. } => (0, 0),
+        };
+        let composite_options = ops::Composite2Options {
+            x,
+            y,
+            ..ops::Composite2Options::default()
+        };
+        let image = ops::composite_2_with_opts(frame, &overlay, ops::BlendMode::Over, &composite_options)?;
+        // composite always adds an alpha band; don't hand one back for images that had none
+        if !frame.image_hasalpha() {
+            let extract_options = ops::ExtractBandOptions {
+                n: image.get_bands() - 1,
+            };
+            return ops::extract_band_with_opts(&image, 0, &extract_options)
+        }
+        return Ok(image)
+    })
 }
 
-fn calculate_crop(
+// top-left corner of a single overlay, from the gravity and the offsets
+fn watermark_position(base: &VipsImage, overlay: &VipsImage, watermark_options: &WatermarkOptions) -> (i32, i32) {
+    let gravity = watermark_options.gravity;
+    let (left, top) = calculate_crop(
+        base.get_width(),
+        base.get_height(),
+        overlay.get_width(),
+        overlay.get_height(),
+        gravity,
+    );
+    let x = match gravity {
+        Gravity::East | Gravity::NorthEast | Gravity::SouthEast => left - watermark_options.x,
+        _ => left + watermark_options.x,
+    };
+    let y = match gravity {
+        Gravity::South | Gravity::SouthEast | Gravity::SouthWest => top - watermark_options.y,
+        _ => top + watermark_options.y,
+    };
+    (x, y)
+}
+
+/// rotates `overlay`, pads it with `spacing` transparent pixels and repeats it into a `width` x `height` layer
+pub(crate) fn tile_overlay(overlay: &VipsImage, width: i32, height: i32, angle: f64, spacing: i32) -> Result<VipsImage> {
+    if spacing < 0 {
+        return Err(Error::OperationError("Watermark spacing must not be negative"))
+    }
+    let rotate_options = RotateOptions {
+        background: vec![0.0; overlay.get_bands() as usize],
+        ..RotateOptions::default()
+    };
+    let tile = rotate_image(overlay, angle, &rotate_options)?;
+    // extend defaults to black, which is fully transparent once there is an alpha band
+    let tile = ops::embed(
+        &tile,
+        spacing / 2,
+        spacing / 2,
+        tile.get_width() + spacing,
+        tile.get_height() + spacing,
+    )?;
+    let across = (width + tile.get_width() - 1) / tile.get_width();
+    let down = (height + tile.get_height() - 1) / tile.get_height();
+    let layer = ops::replicate(&tile, across, down)?;
+    return ops::extract_area(&layer, 0, 0, width, height)
+}
+
+/// A queue of steps applied to one decoded image, which is encoded once at the end.
+/// Chaining the buffer based actions decodes and re-encodes the image at every step instead
+#[derive(Clone, Debug, Default)]
+pub struct Pipeline {
+    options: ActionOptions,
+    steps: Vec<Step>,
+    format: Option<ImageFormat>,
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    Crop(i32, i32, Gravity),
+    Resize(i32, i32, ResizeOptions),
+    Rotate(f64, RotateOptions),
+    WatermarkText(String, i32, String, WatermarkOptions),
+    WatermarkImage(Vec<u8>, WatermarkOptions),
+    Colourspace(ops::Interpretation),
+    Sharpen(ops::SharpenOptions),
+    Strip(StripPolicy),
+}
+
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        Pipeline::default()
+    }
+
+    /// `options` apply when the input is decoded
+    pub fn with_opts(options: &ActionOptions) -> Pipeline {
+        Pipeline {
+            options: options.clone(),
+            ..Pipeline::default()
+        }
+    }
+
+    /// see `crop`
+    pub fn crop(mut self, width: i32, height: i32, gravity: Gravity) -> Pipeline {
+        self.steps.push(Step::Crop(width, height, gravity));
+        self
+    }
+
+    /// scales the image to fit inside `width` x `height`. A `height` of 0 means the same as `width`
+    pub fn resize(self, width: i32, height: i32) -> Pipeline {
+        self.resize_with_opts(width, height, &ResizeOptions::default())
+    }
+
+    /// see `resize_with_opts`
+    pub fn resize_with_opts(mut self, width: i32, height: i32, resize_options: &ResizeOptions) -> Pipeline {
+        self.steps.push(Step::Resize(width, height, resize_options.clone()));
+        self
+    }
+
+    /// see `rotate_with_opts`
+    pub fn rotate(mut self, angle: f64, rotate_options: &RotateOptions) -> Pipeline {
+        self.steps.push(Step::Rotate(angle, rotate_options.clone()));
+        self
+    }
+
+    /// see `watermark_text_with_opts`
+    pub fn watermark_text(
+        mut self,
+        text: &str,
+        dpi: i32,
+        color: &str,
+        watermark_options: &WatermarkOptions,
+    ) -> Pipeline {
+        self.steps.push(Step::WatermarkText(
+            text.to_string(),
+            dpi,
+            color.to_string(),
+            watermark_options.clone(),
+        ));
+        self
+    }
+
+    /// see `watermark_image_with_opts`
+    pub fn watermark_image(mut self, overlay: &[u8], watermark_options: &WatermarkOptions) -> Pipeline {
+        self.steps.push(Step::WatermarkImage(overlay.to_vec(), watermark_options.clone()));
+        self
+    }
+
+    /// converts the pixels to `space`
+    pub fn colourspace(mut self, space: ops::Interpretation) -> Pipeline {
+        self.steps.push(Step::Colourspace(space));
+        self
+    }
+
+    pub fn sharpen(mut self, sharpen_options: &ops::SharpenOptions) -> Pipeline {
+        self.steps.push(Step::Sharpen(sharpen_options.clone()));
+        self
+    }
+
+    /// see `strip_metadata`
+    pub fn strip(mut self, policy: StripPolicy) -> Pipeline {
+        self.steps.push(Step::Strip(policy));
+        self
+    }
+
+    /// encodes the result as `format_type` instead of the input's format
+    pub fn format(mut self, format_type: ImageFormat) -> Pipeline {
+        self.format = Some(format_type);
+        self
+    }
+
+    /// decodes `buf`, applies the steps in the order they were added and encodes the result
+    pub fn run(&self, buf: &[u8]) -> Result<Vec<u8>> {
+        let _type = match self.format {
+            Some(format_type) if !format_type.can_save() => {
+                return Err(Error::UnsupportedFormat(format_type.name()))
+            }
+            Some(format_type) => format_type.name().to_string(),
+            None => vips_image_type(buf),
+        };
+        let mut image = load(buf, &self.options)?;
+        let mut encode_options = self.options.encode.clone();
+        for step in self.steps.iter() {
+            image = match step {
+                Step::Crop(width, height, gravity) => crop_image(&image, *width, *height, *gravity)?,
+                Step::Resize(width, height, resize_options) => {
+                    resize_image(&image, *width, *height, resize_options, &self.options)?
+                }
+                Step::Rotate(angle, rotate_options) => rotate_image(&image, *angle, rotate_options)?,
+                Step::WatermarkText(text, dpi, color, watermark_options) => {
+                    let overlay = text_overlay(text, *dpi, color)?;
+                    composite_watermark(&image, &overlay, watermark_options)?
+                }
+                Step::WatermarkImage(overlay, watermark_options) => {
+                    let overlay = load_first_frame(overlay, &self.options)?;
+                    composite_watermark(&image, &overlay, watermark_options)?
+                }
+                Step::Colourspace(space) => ops::colourspace(&image, *space)?,
+                Step::Sharpen(sharpen_options) => ops::sharpen_with_opts(&image, sharpen_options)?,
+                Step::Strip(policy) => {
+                    encode_options.strip = self.options.encode.strip || *policy == StripPolicy::StripAll;
+                    strip_image(&image, *policy)?
+                }
+            };
+        }
+        return save(&image, &_type, &encode_options)
+    }
+}
+
+/// returns the top-left corner of an `out_width` x `out_height` area anchored at `gravity`
+pub(crate) fn calculate_crop(
     in_width: i32,
     in_height: i32,
     out_width: i32,
     out_height: i32,
-    gravity: i32,
+    gravity: Gravity,
 ) -> (i32, i32) {
-    let mut left = 0;
-    let mut top = 0;
+    let centre_left = (in_width - out_width + 1) / 2;
+    let centre_top = (in_height - out_height + 1) / 2;
+    let right = in_width - out_width;
+    let bottom = in_height - out_height;
     match gravity {
-        1 => {
-            left = (in_width - out_width + 1) / 2;
-        }
-        2 => {
-            left = in_width - out_width;
-            top = (in_height - out_height + 1) / 2;
-        }
-        3 => {
-            left = (in_width - out_width + 1) / 2;
-            top = in_height - out_height;
-        }
-        4 => {
-            top = (in_width - out_width + 1) / 2;
+        Gravity::North => (centre_left, 0),
+        Gravity::East => (right, centre_top),
+        Gravity::South => (centre_left, bottom),
+        Gravity::West => (0, centre_top),
+        Gravity::NorthEast => (right, 0),
+        Gravity::SouthEast => (right, bottom),
+        Gravity::SouthWest => (0, bottom),
+        Gravity::NorthWest => (0, 0),
+        Gravity::Centre | Gravity::Entropy | Gravity::Attention => (centre_left, centre_top),
+    }
+}
+
+lazy_static! {
+    // loaded once, every save with a profile compares against it
+    static ref SRGB_PROFILE: Option<Vec<u8>> = ops::profile_load("srgb").ok();
+}
+
+lazy_static! {
+    // heifsave is there whenever libheif is, but AVIF also needs libheif to be built with an AV1 encoder,
+    // which only shows when something is encoded
+    static ref AV1_ENCODER: bool = {
+        if vips_type_find("VipsOperation", "heifsave_buffer") == 0 {
+            return false
         }
-        _ => {
-            left = (in_width - out_width + 1) / 2;
-            top = (in_height - out_height + 1) / 2;
+        let probe = || -> Result<Vec<u8>> {
+            let pixel = ops::black(1, 1)?;
+            let heifsave_options = ops::HeifsaveBufferOptions {
+                compression: ops::ForeignHeifCompression::Av1,
+                background: vec![0.0],
+                ..ops::HeifsaveBufferOptions::default()
+            };
+            ops::heifsave_buffer_with_opts(&pixel, &heifsave_options)
+        };
+        let available = probe().is_ok();
+        if !available {
+            // a missing encoder isn't an error for the caller
+            unsafe { bindings::vips_error_clear() };
         }
-    }
-    return (left, top);
-} 
+        available
+    };
+}
 
 lazy_static! {
     static ref ImageTypes: HashMap<String, bool> = {
@@ -173,136 +1835,160 @@ lazy_static! {
         map.insert("MAGICK".to_string(), true);
         map.insert("HEIF".to_string(), true);
         map.insert("AVIF".to_string(), true);
+        map.insert("JXL".to_string(), true);
+        map.insert("BMP".to_string(), true);
+        map.insert("ICO".to_string(), true);
+        map.insert("PSD".to_string(), true);
         map
     };
 }
 
-fn vips_image_type(buf: &[u8]) -> String {
-    if buf.len() < 12 {
-        return "unknown".to_string();
-    }
-    if buf[0] == 0xFF && buf[1] == 0xD8 && buf[2] == 0xFF {
-        return "jpeg".to_string();
+/// the format of an encoded image, from its content. Known signatures are matched first, whether or not
+/// this libvips build can load them; anything else is left to the loaders libvips has (`vips_foreign_find_load_buffer`).
+/// Use it to check uploads before decoding them
+pub fn detect_format(buf: &[u8]) -> Option<ImageFormat> {
+    return sniff_format(buf).or_else(|| vips_find_load_format(buf))
+}
+
+// the format name of a buffer for the actions, "unknown" when libvips can't load it
+pub(crate) fn vips_image_type(buf: &[u8]) -> String {
+    match detect_format(buf) {
+        Some(format) if format.can_load() => format.name().to_string(),
+        _ => "unknown".to_string(),
     }
+}
 
-    if is_type_supported("GIF") && buf[0] == 0x47 && buf[1] == 0x49 && buf[2] == 0x46 {
-        return "gif".to_string();
+/// matches the magic numbers of the formats in `ImageFormat`
+pub(crate) fn sniff_format(buf: &[u8]) -> Option<ImageFormat> {
+    let starts_with = |offset: usize, signature: &[u8]| {
+        buf.len() >= offset + signature.len() && &buf[offset..offset + signature.len()] == signature
+    };
+    if starts_with(0, &[0xFF, 0xD8, 0xFF]) {
+        return Some(ImageFormat::Jpeg)
     }
-    if buf[0] == 0x89 && buf[1] == 0x50 && buf[2] == 0x4E && buf[3] == 0x47 {
-        return "png".to_string();
+    if starts_with(0, &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        return Some(ImageFormat::Png)
     }
-    if is_type_supported("TIFF")
-        && ((buf[0] == 0x49 && buf[1] == 0x49 && buf[2] == 0x2A && buf[3] == 0x0)
-            || (buf[0] == 0x4D && buf[1] == 0x4D && buf[2] == 0x0 && buf[3] == 0x2A))
-    {
-        return "tiff".to_string();
+    if starts_with(0, b"GIF87a") || starts_with(0, b"GIF89a") {
+        return Some(ImageFormat::Gif)
     }
-    if is_type_supported("PDF")
-        && buf[0] == 0x25
-        && buf[1] == 0x50
-        && buf[2] == 0x44
-        && buf[3] == 0x46
-    {
-        return "pdf".to_string();
+    if starts_with(0, &[b'I', b'I', 0x2A, 0x00]) || starts_with(0, &[b'M', b'M', 0x00, 0x2A]) {
+        return Some(ImageFormat::Tiff)
     }
-    if is_type_supported("WEBP")
-        && buf[8] == 0x57
-        && buf[9] == 0x45
-        && buf[10] == 0x42
-        && buf[11] == 0x50
-    {
-        return "webp".to_string();
-    }
-    // TODO: implement is_svg_image
-    //if IsTypeSupported(SVG) && IsSVGImage(buf) {
-    //	return SVG
-    //}
-
-    // TODO: Not implement magick
-    //if IsTypeSupported(MAGICK) && strings.HasSuffix(readImageType(buf), "MagickBuffer") {
-    //	return MAGICK
-    //}
-    //
-    // NOTE: libheif currently only supports heic sub types; see:
-    //   https://github.com/strukturag/libheif/issues/83#issuecomment-421427091
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x68
-        && buf[9] == 0x65
-        && buf[10] == 0x69
-        && buf[11] == 0x63
-    {
-        // This is a HEIC file, ftypheic
-        return "heif".to_string();
-    }
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x6d
-        && buf[9] == 0x69
-        && buf[10] == 0x66
-        && buf[11] == 0x31
-    {
-        // This is a HEIF file, ftypmif1
-        return "heif".to_string();
-    }
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x6d
-        && buf[9] == 0x73
-        && buf[10] == 0x66
-        && buf[11] == 0x31
-    {
-        // This is a HEIFS file, ftypmsf1
-        return "heif".to_string();
-    }
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x68
-        && buf[9] == 0x65
-        && buf[10] == 0x69
-        && buf[11] == 0x73
-    {
-        // This is a HEIFS file, ftypheis
-        return "heif".to_string();
-    }
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x68
-        && buf[9] == 0x65
-        && buf[10] == 0x76
-        && buf[11] == 0x63
-    {
-        // This is a HEIFS file, ftyphevc
-        return "heif".to_string();
-    }
-    if is_type_supported("HEIF")
-        && buf[4] == 0x66
-        && buf[5] == 0x74
-        && buf[6] == 0x79
-        && buf[7] == 0x70
-        && buf[8] == 0x61
-        && buf[9] == 0x76
-        && buf[10] == 0x69
-        && buf[11] == 0x66
+    if starts_with(0, b"RIFF") && starts_with(8, b"WEBP") {
+        return Some(ImageFormat::Webp)
+    }
+    if starts_with(0, b"%PDF") {
+        return Some(ImageFormat::Pdf)
+    }
+    // codestream, then the ISO BMFF container
+    if starts_with(0, &[0xFF, 0x0A])
+        || starts_with(0, &[0x00, 0x00, 0x00, 0x0C, b'J', b'X', b'L', b' ', 0x0D, 0x0A, 0x87, 0x0A])
     {
-        return "avif".to_string();
+        return Some(ImageFormat::Jxl)
+    }
+    if starts_with(4, b"ftyp") {
+        return sniff_ftyp(buf)
+    }
+    if starts_with(0, b"8BPS") && (starts_with(4, &[0x00, 0x01]) || starts_with(4, &[0x00, 0x02])) {
+        return Some(ImageFormat::Psd)
+    }
+    // reserved, type 1 (icon) and at least one image
+    if starts_with(0, &[0x00, 0x00, 0x01, 0x00]) && buf.len() >= 6 && (buf[4] != 0 || buf[5] != 0) {
+        return Some(ImageFormat::Ico)
+    }
+    // "BM" alone is too common, so the DIB header size has to be one of the known ones too
+    if starts_with(0, b"BM") && buf.len() >= 18 {
+        let dib_size = u32::from_le_bytes([buf[14], buf[15], buf[16], buf[17]]);
+        if [12, 40, 52, 56, 64, 108, 124].contains(&dib_size) {
+            return Some(ImageFormat::Bmp)
+        }
+    }
+    if is_svg(buf) {
+        return Some(ImageFormat::Svg)
+    }
+    return None
+}
+
+// HEIF and AVIF share the ftyp box; the major brand or, for the generic mif1/msf1, the compatible brands tell them apart
+fn sniff_ftyp(buf: &[u8]) -> Option<ImageFormat> {
+    if buf.len() < 12 {
+        return None
+    }
+    let box_size = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    let box_end = cmp::min(cmp::max(box_size, 16), buf.len());
+    let major_brand = &buf[8..12];
+    let compatible_brands = if box_end > 16 { &buf[16..box_end] } else { &[][..] };
+    let has_brand = |brand: &[u8]| compatible_brands.chunks(4).any(|b| b == brand);
+    match major_brand {
+        b"avif" | b"avis" => Some(ImageFormat::Avif),
+        b"heic" | b"heix" | b"hevc" | b"hevx" | b"heim" | b"heis" | b"hevm" | b"hevs" => Some(ImageFormat::Heif),
+        b"mif1" | b"msf1" if has_brand(b"avif") || has_brand(b"avis") => Some(ImageFormat::Avif),
+        b"mif1" | b"msf1" => Some(ImageFormat::Heif),
+        _ => None,
+    }
+}
+
+// an XML or SVG document whose root element, after the prolog, comments and doctype, is <svg>
+fn is_svg(buf: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&buf[..cmp::min(buf.len(), 4096)]);
+    let mut rest = head.trim_start_matches('\u{feff}').trim_start();
+    loop {
+        if rest.starts_with("<?") {
+            rest = match rest.find("?>") {
+                Some(end) => rest[end + 2..].trim_start(),
+                None => return false,
+            };
+        } else if rest.starts_with("<!--") {
+            rest = match rest.find("-->") {
+                Some(end) => rest[end + 3..].trim_start(),
+                None => return false,
+            };
+        } else if rest.starts_with("<!") {
+            rest = match rest.find('>') {
+                Some(end) => rest[end + 1..].trim_start(),
+                None => return false,
+            };
+        } else {
+            return rest.starts_with("<svg")
+        }
+    }
+}
+
+// the buffer loaders libvips may pick, by class name, and the format each one reads. Names changed as
+// libraries were swapped, e.g. giflib for nsgif in 8.11 and libpng for spng. ImageMagick's loaders
+// (VipsForeignLoadMagickBuffer, VipsForeignLoadMagick7Buffer) read many formats and can't name one
+const LOADER_FORMATS: [(&str, ImageFormat); 13] = [
+    ("VipsForeignLoadJpegBuffer", ImageFormat::Jpeg),
+    ("VipsForeignLoadPngBuffer", ImageFormat::Png),
+    ("VipsForeignLoadSpngBuffer", ImageFormat::Png),
+    ("VipsForeignLoadWebpBuffer", ImageFormat::Webp),
+    ("VipsForeignLoadTiffBuffer", ImageFormat::Tiff),
+    ("VipsForeignLoadGifBuffer", ImageFormat::Gif),
+    ("VipsForeignLoadNsgifBuffer", ImageFormat::Gif),
+    ("VipsForeignLoadPdfBuffer", ImageFormat::Pdf),
+    ("VipsForeignLoadPopplerBuffer", ImageFormat::Pdf),
+    ("VipsForeignLoadPdfiumBuffer", ImageFormat::Pdf),
+    ("VipsForeignLoadSvgBuffer", ImageFormat::Svg),
+    ("VipsForeignLoadHeifBuffer", ImageFormat::Heif),
+    ("VipsForeignLoadJxlBuffer", ImageFormat::Jxl),
+];
+
+// asks every loader libvips has whether it recognises the buffer
+fn vips_find_load_format(buf: &[u8]) -> Option<ImageFormat> {
+    unsafe {
+        let loader = bindings::vips_foreign_find_load_buffer(buf.as_ptr() as *const c_void, buf.len() as u64);
+        if loader.is_null() {
+            // not finding a loader isn't an error for the caller
+            bindings::vips_error_clear();
+            return None
+        }
+        let loader = CStr::from_ptr(loader).to_string_lossy();
+        return LOADER_FORMATS
+            .iter()
+            .find(|(name, _)| *name == loader)
+            .map(|(_, format)| *format)
     }
-    return "unknown".to_string();
 }
 
 fn is_type_supported(t: &str) -> bool {
@@ -320,10 +2006,25 @@ fn vips_type_find_bridge(t: &str) -> u64 {
         "JPEG" => return vips_type_find("VipsOperation", "jpegload"),
         "MAGICK" => return vips_type_find("VipsOperation", "magickload"),
         "HEIF" => return vips_type_find("VipsOperation", "heifload"),
+        "AVIF" => return vips_type_find("VipsOperation", "heifload"),
+        "JXL" => return vips_type_find("VipsOperation", "jxlload"),
+        "BMP" | "ICO" | "PSD" => return vips_type_find("VipsOperation", "magickload"),
         _ => return 0,
     }
 }
 
+// the libvips gif saver, from 8.12. A non-zero interframe_maxerror also needs 8.13, `ops::gifsave_buffer`
+// returns an error without it
+fn gifsave_available() -> bool {
+    return vips_version_at_least(8, 12) && vips_type_find("VipsOperation", "gifsave_buffer") != 0
+}
+
+// compares against the libvips that is loaded at runtime, not the headers the bindings were made from
+fn vips_version_at_least(major: i32, minor: i32) -> bool {
+    let version = unsafe { (bindings::vips_version(0), bindings::vips_version(1)) };
+    return version >= (major, minor)
+}
+
 fn vips_type_find(name: &str, nickname: &str) -> u64 {
     unsafe {
         let name_in = CString::new(name).unwrap();
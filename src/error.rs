@@ -4,6 +4,7 @@ pub enum Error {
     InitializationError(&'static str),
     OperationError(&'static str),
     IOError(&'static str),
+    UnsupportedFormat(&'static str),
     LinearError,
     CaseError,
     GetpointError,
@@ -132,11 +133,17 @@ pub enum Error {
     GifloadError,
     GifloadBufferError,
     GifloadSourceError,
+    GifsaveError,
+    GifsaveBufferError,
+    GifsaveTargetError,
     PngloadError,
     PngloadBufferError,
     PngloadSourceError,
     JpegloadError,
     JpegloadBufferError,
+    JxlloadError,
+    JxlloadBufferError,
+    JxlloadSourceError,
     WebploadError,
     WebploadBufferError,
     WebploadSourceError,
@@ -146,6 +153,9 @@ pub enum Error {
     HeifloadError,
     HeifloadBufferError,
     HeifloadSourceError,
+    PdfloadError,
+    PdfloadBufferError,
+    PdfloadSourceError,
     CsvsaveError,
     CsvsaveTargetError,
     MatrixsaveError,
@@ -167,6 +177,9 @@ pub enum Error {
     JpegsaveBufferError,
     JpegsaveTargetError,
     JpegsaveMimeError,
+    JxlsaveError,
+    JxlsaveBufferError,
+    JxlsaveTargetError,
     WebpsaveError,
     WebpsaveBufferError,
     WebpsaveTargetError,
@@ -282,6 +295,11 @@ impl std::fmt::Display for Error {
             }
             Error::OperationError(msg) => write!(f, "vips error: OperationError - {}", msg),
             Error::IOError(msg) => write!(f, "vips error: IOError - {}", msg),
+            Error::UnsupportedFormat(format) => write!(
+                f,
+                "vips error: UnsupportedFormat - {} is not supported by this libvips build",
+                format
+            ),
             Error::LinearError => write!(
                 f,
                 "vips error: LinearError. Check error buffer for more details"
@@ -794,6 +812,18 @@ impl std::fmt::Display for Error {
                 f,
                 "vips error: GifloadSourceError. Check error buffer for more details"
             ),
+            Error::GifsaveError => write!(
+                f,
+                "vips error: GifsaveError. Check error buffer for more details"
+            ),
+            Error::GifsaveBufferError => write!(
+                f,
+                "vips error: GifsaveBufferError. Check error buffer for more details"
+            ),
+            Error::GifsaveTargetError => write!(
+                f,
+                "vips error: GifsaveTargetError. Check error buffer for more details"
+            ),
             Error::PngloadError => write!(
                 f,
                 "vips error: PngloadError. Check error buffer for more details"
@@ -814,6 +844,18 @@ impl std::fmt::Display for Error {
                 f,
                 "vips error: JpegloadBufferError. Check error buffer for more details"
             ),
+            Error::JxlloadError => write!(
+                f,
+                "vips error: JxlloadError. Check error buffer for more details"
+            ),
+            Error::JxlloadBufferError => write!(
+                f,
+                "vips error: JxlloadBufferError. Check error buffer for more details"
+            ),
+            Error::JxlloadSourceError => write!(
+                f,
+                "vips error: JxlloadSourceError. Check error buffer for more details"
+            ),
             Error::WebploadError => write!(
                 f,
                 "vips error: WebploadError. Check error buffer for more details"
@@ -850,6 +892,18 @@ impl std::fmt::Display for Error {
                 f,
                 "vips error: HeifloadSourceError. Check error buffer for more details"
             ),
+            Error::PdfloadError => write!(
+                f,
+                "vips error: PdfloadError. Check error buffer for more details"
+            ),
+            Error::PdfloadBufferError => write!(
+                f,
+                "vips error: PdfloadBufferError. Check error buffer for more details"
+            ),
+            Error::PdfloadSourceError => write!(
+                f,
+                "vips error: PdfloadSourceError. Check error buffer for more details"
+            ),
             Error::CsvsaveError => write!(
                 f,
                 "vips error: CsvsaveError. Check error buffer for more details"
@@ -934,6 +988,18 @@ impl std::fmt::Display for Error {
                 f,
                 "vips error: JpegsaveMimeError. Check error buffer for more details"
             ),
+            Error::JxlsaveError => write!(
+                f,
+                "vips error: JxlsaveError. Check error buffer for more details"
+            ),
+            Error::JxlsaveBufferError => write!(
+                f,
+                "vips error: JxlsaveBufferError. Check error buffer for more details"
+            ),
+            Error::JxlsaveTargetError => write!(
+                f,
+                "vips error: JxlsaveTargetError. Check error buffer for more details"
+            ),
             Error::WebpsaveError => write!(
                 f,
                 "vips error: WebpsaveError. Check error buffer for more details"
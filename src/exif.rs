@@ -0,0 +1,249 @@
+// (c) Copyright 2019-2020 OLX
+// typed access to the `exif-ifd*` header fields libvips creates when loading an image.
+// libvips formats each EXIF entry as `<raw value> (<readable value>, <format>, <n> components, <n> bytes)`,
+// e.g. `52/1 31/1 1234/100 (52, 31, 12.34, Rational, 3 components, 24 bytes)`, and the raw value is what gets decoded here.
+use crate::VipsImage;
+
+/// An unsigned or signed EXIF rational
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rational {
+    pub numerator: i64,
+    pub denominator: i64,
+}
+
+impl Rational {
+    /// returns `None` for a zero denominator
+    pub fn to_f64(&self) -> Option<f64> {
+        if self.denominator == 0 {
+            None
+        } else {
+            Some(self.numerator as f64 / self.denominator as f64)
+        }
+    }
+}
+
+/// EXIF orientation tag (0x0112). The names describe where row 0 and column 0 of the stored image are
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// no transform needed
+    TopLeft = 1,
+    /// flipped horizontally
+    TopRight = 2,
+    /// rotated 180°
+    BottomRight = 3,
+    /// flipped vertically
+    BottomLeft = 4,
+    /// transposed
+    LeftTop = 5,
+    /// needs a 90° clockwise rotation to display
+    RightTop = 6,
+    /// transversed
+    RightBottom = 7,
+    /// needs a 270° clockwise rotation to display
+    LeftBottom = 8,
+}
+
+impl Orientation {
+    /// maps the numeric tag value, 1 to 8
+    pub fn from_i32(value: i32) -> Option<Orientation> {
+        match value {
+            1 => Some(Orientation::TopLeft),
+            2 => Some(Orientation::TopRight),
+            3 => Some(Orientation::BottomRight),
+            4 => Some(Orientation::BottomLeft),
+            5 => Some(Orientation::LeftTop),
+            6 => Some(Orientation::RightTop),
+            7 => Some(Orientation::RightBottom),
+            8 => Some(Orientation::LeftBottom),
+            _ => None,
+        }
+    }
+}
+
+/// Decoded EXIF data. Every field is `None` when the tag is missing or can't be decoded
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Exif {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub software: Option<String>,
+    pub copyright: Option<String>,
+    pub orientation: Option<Orientation>,
+    /// `DateTime` as seconds since the unix epoch. EXIF times carry no zone, so they are read as UTC
+    pub datetime: Option<i64>,
+    /// `DateTimeOriginal` as seconds since the unix epoch, read as UTC
+    pub datetime_original: Option<i64>,
+    pub exposure_time: Option<Rational>,
+    pub f_number: Option<Rational>,
+    pub iso: Option<i64>,
+    pub focal_length: Option<Rational>,
+    /// signed decimal degrees, negative south of the equator
+    pub gps_latitude: Option<f64>,
+    /// signed decimal degrees, negative west of Greenwich
+    pub gps_longitude: Option<f64>,
+    /// metres, negative below sea level
+    pub gps_altitude: Option<f64>,
+}
+
+impl Exif {
+    /// decodes the `exif-ifd*` header fields of an image
+    pub fn from_image(image: &VipsImage) -> Exif {
+        let field = |name: &str| image.get_string(name).ok();
+        let value = |name: &str| field(name).map(|f| exif_value(&f).to_string());
+
+        let gps_latitude = value("exif-ifd3-GPSLatitude").and_then(|v| {
+            parse_gps_coordinate(&v, &value("exif-ifd3-GPSLatitudeRef").unwrap_or_default())
+        });
+        let gps_longitude = value("exif-ifd3-GPSLongitude").and_then(|v| {
+            parse_gps_coordinate(&v, &value("exif-ifd3-GPSLongitudeRef").unwrap_or_default())
+        });
+        let gps_altitude = value("exif-ifd3-GPSAltitude")
+            .and_then(|v| parse_rational(&v))
+            .and_then(|r| r.to_f64())
+            .map(
+                |altitude| match value("exif-ifd3-GPSAltitudeRef").as_deref() {
+                    Some("1") => -altitude,
+                    _ => altitude,
+                },
+            );
+
+        Exif {
+            make: value("exif-ifd0-Make").filter(|v| !v.is_empty()),
+            model: value("exif-ifd0-Model").filter(|v| !v.is_empty()),
+            software: value("exif-ifd0-Software").filter(|v| !v.is_empty()),
+            copyright: value("exif-ifd0-Copyright").filter(|v| !v.is_empty()),
+            orientation: value("exif-ifd0-Orientation")
+                .and_then(|v| v.parse::<i32>().ok())
+                .and_then(Orientation::from_i32),
+            datetime: value("exif-ifd0-DateTime").and_then(|v| parse_datetime(&v)),
+            datetime_original: value("exif-ifd2-DateTimeOriginal").and_then(|v| parse_datetime(&v)),
+            exposure_time: value("exif-ifd2-ExposureTime").and_then(|v| parse_rational(&v)),
+            f_number: value("exif-ifd2-FNumber").and_then(|v| parse_rational(&v)),
+            iso: value("exif-ifd2-ISOSpeedRatings")
+                .and_then(|v| v.split_whitespace().next()?.parse().ok()),
+            focal_length: value("exif-ifd2-FocalLength").and_then(|v| parse_rational(&v)),
+            gps_latitude,
+            gps_longitude,
+            gps_altitude,
+        }
+    }
+}
+
+/// extracts the raw value from a libvips EXIF string, dropping the `(readable, format, components, bytes)` suffix
+pub fn exif_value(field: &str) -> &str {
+    let field = field.trim_end();
+    let body = match field.strip_suffix(')') {
+        Some(body) => body,
+        None => return field.trim(),
+    };
+    // format, components and bytes never contain ", ", but the readable value may
+    let rest = match body.rsplitn(4, ", ").nth(3) {
+        Some(rest) => rest,
+        None => return field.trim(),
+    };
+    // for strings the readable value repeats the raw one, which can contain parentheses itself
+    for (index, _) in rest.match_indices('(') {
+        if rest[..index].trim_end() == &rest[index + 1..] {
+            return rest[..index].trim();
+        }
+    }
+    match rest.find('(') {
+        Some(index) => rest[..index].trim(),
+        None => rest.trim(),
+    }
+}
+
+/// parses the first rational of a raw value, like `28/10`
+pub fn parse_rational(value: &str) -> Option<Rational> {
+    parse_rationals(value).into_iter().next()
+}
+
+/// parses a space separated list of rationals, like `52/1 31/1 1234/100`.
+/// Plain integers are read as rationals with a denominator of 1
+pub fn parse_rationals(value: &str) -> Vec<Rational> {
+    value
+        .split_whitespace()
+        .map(|part| {
+            let mut split = part.splitn(2, '/');
+            let numerator = split.next()?.parse().ok()?;
+            let denominator = match split.next() {
+                Some(d) => d.parse().ok()?,
+                None => 1,
+            };
+            Some(Rational {
+                numerator,
+                denominator,
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default()
+}
+
+/// converts a degrees/minutes/seconds GPS value to signed decimal degrees.
+/// `reference` is the matching `GPSLatitudeRef`/`GPSLongitudeRef`; `S` and `W` give negative values
+pub fn parse_gps_coordinate(value: &str, reference: &str) -> Option<f64> {
+    let parts = parse_rationals(value);
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let mut degrees = 0.0;
+    let mut divisor = 1.0;
+    for part in parts.iter() {
+        degrees += part.to_f64()? / divisor;
+        divisor *= 60.0;
+    }
+    match reference.trim() {
+        "S" | "s" | "W" | "w" => Some(-degrees),
+        _ => Some(degrees),
+    }
+}
+
+/// parses an EXIF `YYYY:MM:DD HH:MM:SS` date as UTC and returns seconds since the unix epoch
+pub fn parse_datetime(value: &str) -> Option<i64> {
+    let mut parts = value.trim().splitn(2, ' ');
+    let date: Vec<i64> = parts
+        .next()?
+        .split(':')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let time: Vec<i64> = parts
+        .next()?
+        .trim()
+        .split(':')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    if date.len() != 3 || time.len() != 3 {
+        return None;
+    }
+    let (year, month, day) = (date[0], date[1], date[2]);
+    let (hour, minute, second) = (time[0], time[1], time[2]);
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0..=60).contains(&second)
+    {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+// length of `month` (1 to 12) in the proleptic gregorian calendar
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// days since 1970-01-01 for a proleptic gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
@@ -43,6 +43,91 @@ pub struct VipsTarget {
     pub(crate) ctx: *mut bindings::VipsTarget,
 }
 
+/// The type of a header field, as reported by `vips_image_get_typeof`
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderType {
+    /// ints, booleans and enums like `interpretation`
+    Int,
+    Double,
+    /// plain and ref-counted strings, like the `exif-ifd*` fields
+    String,
+    Blob,
+    ArrayInt,
+    ArrayDouble,
+    Image,
+    /// any other GType, with its type name
+    Other(String),
+}
+
+/// The value of a header field
+#[derive(Debug)]
+pub enum HeaderValue {
+    Int(i32),
+    Double(f64),
+    String(String),
+    Blob(Vec<u8>),
+    ArrayInt(Vec<i32>),
+    ArrayDouble(Vec<f64>),
+    Image(VipsImage),
+    /// a field of a type without a rust mapping, with its type name
+    Other(String),
+}
+
+// a copied `VipsImage` would be unreffed twice, so the clone takes a reference of its own
+impl Clone for HeaderValue {
+    fn clone(&self) -> Self {
+        match self {
+            HeaderValue::Int(value) => HeaderValue::Int(*value),
+            HeaderValue::Double(value) => HeaderValue::Double(*value),
+            HeaderValue::String(value) => HeaderValue::String(value.clone()),
+            HeaderValue::Blob(value) => HeaderValue::Blob(value.clone()),
+            HeaderValue::ArrayInt(value) => HeaderValue::ArrayInt(value.clone()),
+            HeaderValue::ArrayDouble(value) => HeaderValue::ArrayDouble(value.clone()),
+            HeaderValue::Image(image) => unsafe {
+                bindings::g_object_ref(image.ctx as *mut c_void);
+                HeaderValue::Image(VipsImage { ctx: image.ctx })
+            },
+            HeaderValue::Other(type_name) => HeaderValue::Other(type_name.clone()),
+        }
+    }
+}
+
+const G_TYPE_BOOLEAN: bindings::GType = 5 << bindings::G_TYPE_FUNDAMENTAL_SHIFT;
+const G_TYPE_INT: bindings::GType = 6 << bindings::G_TYPE_FUNDAMENTAL_SHIFT;
+const G_TYPE_ENUM: bindings::GType = 12 << bindings::G_TYPE_FUNDAMENTAL_SHIFT;
+const G_TYPE_DOUBLE: bindings::GType = 15 << bindings::G_TYPE_FUNDAMENTAL_SHIFT;
+const G_TYPE_STRING: bindings::GType = 16 << bindings::G_TYPE_FUNDAMENTAL_SHIFT;
+
+impl HeaderType {
+    fn from_gtype(gtype: bindings::GType) -> HeaderType {
+        unsafe {
+            let fundamental = bindings::g_type_fundamental(gtype);
+            if fundamental == G_TYPE_INT || fundamental == G_TYPE_BOOLEAN || fundamental == G_TYPE_ENUM {
+                HeaderType::Int
+            } else if fundamental == G_TYPE_DOUBLE {
+                HeaderType::Double
+            } else if fundamental == G_TYPE_STRING || gtype == bindings::vips_ref_string_get_type() {
+                HeaderType::String
+            } else if gtype == bindings::vips_blob_get_type() {
+                HeaderType::Blob
+            } else if gtype == bindings::vips_array_int_get_type() {
+                HeaderType::ArrayInt
+            } else if gtype == bindings::vips_array_double_get_type() {
+                HeaderType::ArrayDouble
+            } else if bindings::g_type_is_a(gtype, bindings::vips_image_get_type()) != 0 {
+                HeaderType::Image
+            } else {
+                let type_name = bindings::g_type_name(gtype);
+                if type_name.is_null() {
+                    HeaderType::Other(String::new())
+                } else {
+                    HeaderType::Other(CStr::from_ptr(type_name).to_string_lossy().into_owned())
+                }
+            }
+        }
+    }
+}
+
 /// This is the main type of vips. It represents an image and most operations will take one as input and output a new one.
 /// In the moment this type is not thread safe. Be careful working within thread environments.
 impl VipsImage {
@@ -335,6 +420,281 @@ impl VipsImage {
         }
     }
 
+    /// get a string header field, like `exif-ifd0-Make`. Fails if the field is missing or is not a string
+    pub fn get_string(&self, name: &str) -> Result<String> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut out: *const c_char = null_mut();
+            let res = bindings::vips_image_get_string(self.ctx, name_c_str.as_ptr(), &mut out);
+            if res != 0 || out.is_null() {
+                return Err(Error::OperationError("Could not get string field from image"));
+            }
+            Ok(CStr::from_ptr(out).to_string_lossy().into_owned())
+        }
+    }
+
+    /// get an integer header field, like `orientation`
+    pub fn get_int(&self, name: &str) -> Result<i32> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut out: i32 = 0;
+            let res = bindings::vips_image_get_int(self.ctx, name_c_str.as_ptr(), &mut out);
+            utils::result(
+                res,
+                out,
+                Error::OperationError("Could not get int field from image"),
+            )
+        }
+    }
+
+    /// get a double header field
+    pub fn get_double(&self, name: &str) -> Result<f64> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut out: f64 = 0.0;
+            let res = bindings::vips_image_get_double(self.ctx, name_c_str.as_ptr(), &mut out);
+            utils::result(
+                res,
+                out,
+                Error::OperationError("Could not get double field from image"),
+            )
+        }
+    }
+
+    /// get a blob header field, like `icc-profile-data` or `exif-data`. The bytes are copied out of the image
+    pub fn get_blob(&self, name: &str) -> Result<Vec<u8>> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut data: *const c_void = null_mut();
+            let mut length: u64 = 0;
+            let res = bindings::vips_image_get_blob(
+                self.ctx,
+                name_c_str.as_ptr(),
+                &mut data,
+                &mut length,
+            );
+            if res != 0 || data.is_null() {
+                return Err(Error::OperationError("Could not get blob field from image"));
+            }
+            Ok(std::slice::from_raw_parts(data as *const u8, length as usize).to_vec())
+        }
+    }
+
+    /// get the names of all header fields set on the image, including the `exif-*`, `xmp-data` and `icc-profile-data` ones
+    pub fn get_fields(&self) -> Vec<String> {
+        unsafe {
+            let fields = bindings::vips_image_get_fields(self.ctx);
+            let mut names = Vec::new();
+            if fields.is_null() {
+                return names;
+            }
+            let mut i = 0;
+            while !(*fields.offset(i)).is_null() {
+                names.push(
+                    CStr::from_ptr(*fields.offset(i))
+                        .to_string_lossy()
+                        .into_owned(),
+                );
+                i += 1;
+            }
+            bindings::g_strfreev(fields);
+            names
+        }
+    }
+
+    /// get the type of a header field. Returns `None` if the field is not set
+    pub fn get_typeof(&self, name: &str) -> Result<Option<HeaderType>> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let gtype = bindings::vips_image_get_typeof(self.ctx, name_c_str.as_ptr());
+            if gtype == 0 {
+                return Ok(None);
+            }
+            Ok(Some(HeaderType::from_gtype(gtype)))
+        }
+    }
+
+    /// get a header field as a typed value
+    pub fn get_value(&self, name: &str) -> Result<HeaderValue> {
+        match self.get_typeof(name)? {
+            None => Err(Error::OperationError("Field is not set on image")),
+            Some(HeaderType::Int) => self.get_int(name).map(HeaderValue::Int),
+            Some(HeaderType::Double) => self.get_double(name).map(HeaderValue::Double),
+            Some(HeaderType::String) => self.get_string(name).map(HeaderValue::String),
+            Some(HeaderType::Blob) => self.get_blob(name).map(HeaderValue::Blob),
+            Some(HeaderType::ArrayInt) => self.get_array_int(name).map(HeaderValue::ArrayInt),
+            Some(HeaderType::ArrayDouble) => {
+                self.get_array_double(name).map(HeaderValue::ArrayDouble)
+            }
+            Some(HeaderType::Image) => self.get_image(name).map(HeaderValue::Image),
+            Some(HeaderType::Other(type_name)) => Ok(HeaderValue::Other(type_name)),
+        }
+    }
+
+    /// get every header field with its typed value, in the order libvips reports them
+    pub fn get_header(&self) -> Result<Vec<(String, HeaderValue)>> {
+        self.get_fields()
+            .into_iter()
+            .map(|name| {
+                let value = self.get_value(&name)?;
+                Ok((name, value))
+            })
+            .collect()
+    }
+
+    /// get an int array header field
+    pub fn get_array_int(&self, name: &str) -> Result<Vec<i32>> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut out: *mut i32 = null_mut();
+            let mut n: i32 = 0;
+            let res =
+                bindings::vips_image_get_array_int(self.ctx, name_c_str.as_ptr(), &mut out, &mut n);
+            if res != 0 || out.is_null() {
+                return Err(Error::OperationError("Could not get int array field from image"));
+            }
+            Ok(utils::new_int_array(out, n as u64))
+        }
+    }
+
+    /// get a double array header field
+    pub fn get_array_double(&self, name: &str) -> Result<Vec<f64>> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut value: bindings::GValue = std::mem::zeroed();
+            let res = bindings::vips_image_get(self.ctx, name_c_str.as_ptr(), &mut value);
+            if res != 0 {
+                return Err(Error::OperationError("Could not get double array field from image"));
+            }
+            let mut n: i32 = 0;
+            let out = bindings::vips_value_get_array_double(&value, &mut n);
+            let array = if out.is_null() {
+                Vec::new()
+            } else {
+                utils::new_double_array(out, n as u64)
+            };
+            bindings::g_value_unset(&mut value);
+            Ok(array)
+        }
+    }
+
+    /// get an image header field
+    pub fn get_image(&self, name: &str) -> Result<VipsImage> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut out: *mut bindings::VipsImage = null_mut();
+            let res = bindings::vips_image_get_image(self.ctx, name_c_str.as_ptr(), &mut out);
+            utils::result(
+                res,
+                VipsImage { ctx: out },
+                Error::OperationError("Could not get image field from image"),
+            )
+        }
+    }
+
+    /// set an integer header field, like `orientation`.
+    /// Images coming out of operations may be shared through the operation cache, so set fields on a copy (`ops::copy`) of them
+    pub fn set_int(&mut self, name: &str, value: i32) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            bindings::vips_image_set_int(self.ctx, name_c_str.as_ptr(), value);
+            Ok(())
+        }
+    }
+
+    /// set a double header field
+    pub fn set_double(&mut self, name: &str, value: f64) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            bindings::vips_image_set_double(self.ctx, name_c_str.as_ptr(), value);
+            Ok(())
+        }
+    }
+
+    /// set a string header field, like `exif-ifd0-Copyright`
+    pub fn set_string(&mut self, name: &str, value: &str) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let value_c_str = utils::new_c_string(value)?;
+            bindings::vips_image_set_string(self.ctx, name_c_str.as_ptr(), value_c_str.as_ptr());
+            Ok(())
+        }
+    }
+
+    /// set a blob header field, like `icc-profile-data`. The bytes are copied into the image
+    pub fn set_blob(&mut self, name: &str, value: &[u8]) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            bindings::vips_image_set_blob_copy(
+                self.ctx,
+                name_c_str.as_ptr(),
+                value.as_ptr() as *const c_void,
+                value.len() as u64,
+            );
+            Ok(())
+        }
+    }
+
+    /// set an int array header field
+    pub fn set_array_int(&mut self, name: &str, value: &[i32]) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            bindings::vips_image_set_array_int(
+                self.ctx,
+                name_c_str.as_ptr(),
+                value.as_ptr(),
+                value.len() as i32,
+            );
+            Ok(())
+        }
+    }
+
+    /// set a double array header field
+    pub fn set_array_double(&mut self, name: &str, value: &[f64]) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            let mut gvalue: bindings::GValue = std::mem::zeroed();
+            bindings::g_value_init(&mut gvalue, bindings::vips_array_double_get_type());
+            bindings::vips_value_set_array_double(&mut gvalue, value.as_ptr(), value.len() as i32);
+            bindings::vips_image_set(self.ctx, name_c_str.as_ptr(), &mut gvalue);
+            bindings::g_value_unset(&mut gvalue);
+            Ok(())
+        }
+    }
+
+    /// set an image header field. The image takes its own reference to `value`
+    pub fn set_image(&mut self, name: &str, value: &VipsImage) -> Result<()> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            bindings::vips_image_set_image(self.ctx, name_c_str.as_ptr(), value.ctx);
+            Ok(())
+        }
+    }
+
+    /// set a header field from a typed value
+    pub fn set_value(&mut self, name: &str, value: &HeaderValue) -> Result<()> {
+        match value {
+            HeaderValue::Int(v) => self.set_int(name, *v),
+            HeaderValue::Double(v) => self.set_double(name, *v),
+            HeaderValue::String(v) => self.set_string(name, v),
+            HeaderValue::Blob(v) => self.set_blob(name, v),
+            HeaderValue::ArrayInt(v) => self.set_array_int(name, v),
+            HeaderValue::ArrayDouble(v) => self.set_array_double(name, v),
+            HeaderValue::Image(v) => self.set_image(name, v),
+            HeaderValue::Other(_) => Err(Error::OperationError(
+                "Cannot set a header field of unknown type",
+            )),
+        }
+    }
+
+    /// remove a header field, like `exif-ifd3-GPSLatitude`. Returns `false` if the field was not set
+    pub fn remove_field(&mut self, name: &str) -> Result<bool> {
+        unsafe {
+            let name_c_str = utils::new_c_string(name)?;
+            Ok(bindings::vips_image_remove(self.ctx, name_c_str.as_ptr()) != 0)
+        }
+    }
+
     pub fn image_set_delete_on_close(&mut self, flag: bool) {
         unsafe {
             bindings::vips_image_set_delete_on_close(self.ctx, if flag { 1 } else { 0 });
@@ -954,7 +1314,8 @@ impl Into<Vec<u8>> for VipsBlob {
         unsafe {
             let mut size: u64 = 0;
             let bytes = bindings::vips_blob_get(self.ctx, &mut size);
-            Vec::from_raw_parts(bytes as *mut u8, size as usize, size as usize)
+            // the blob still owns the bytes and frees them when it is dropped, so copy them out
+            std::slice::from_raw_parts(bytes as *const u8, size as usize).to_vec()
         }
     }
 }
@@ -13,11 +13,13 @@ extern crate lazy_static;
 
 pub mod bindings;
 pub mod error;
+pub mod exif;
 mod image;
 pub mod ops;
 mod utils;
 
 pub mod action;
+pub mod transform;
 
 use error::Error;
 use std::ffi::*;
@@ -130,3 +130,809 @@ pub fn case(index: &VipsImage, cases: &mut [VipsImage], n: i32) -> Result<VipsIm
         )
     }
 }
+
+// an operation built up by name one argument at a time, for the ones newer than the headers these bindings
+// come from: optional arguments left at their default are never set, so a libvips that predates them still runs
+struct VipsCall {
+    operation: *mut bindings::VipsOperation,
+}
+
+impl VipsCall {
+    fn new(name: &str, error: Error) -> Result<VipsCall> {
+        let name_in = utils::new_c_string(name)?;
+        let operation = unsafe { bindings::vips_operation_new(name_in.as_ptr()) };
+        if operation.is_null() {
+            return Err(error);
+        }
+        Ok(VipsCall { operation })
+    }
+
+    fn argument_name(&self, name: &str) -> Result<CString> {
+        let name_in = utils::new_c_string(name)?;
+        let found = unsafe {
+            let mut pspec = null_mut();
+            let mut argument_class = null_mut();
+            let mut argument_instance = null_mut();
+            bindings::vips_object_get_argument(
+                self.operation as *mut bindings::VipsObject,
+                name_in.as_ptr(),
+                &mut pspec,
+                &mut argument_class,
+                &mut argument_instance,
+            ) == 0
+        };
+        if !found {
+            return Err(Error::OperationError("Option is not supported by this libvips version"));
+        }
+        Ok(name_in)
+    }
+
+    fn set_int(&mut self, name: &str, value: i32) -> Result<()> {
+        let name_in = self.argument_name(name)?;
+        unsafe { bindings::g_object_set(self.operation as bindings::gpointer, name_in.as_ptr(), value, NULL) };
+        Ok(())
+    }
+
+    fn set_bool(&mut self, name: &str, value: bool) -> Result<()> {
+        self.set_int(name, if value { 1 } else { 0 })
+    }
+
+    fn set_double(&mut self, name: &str, value: f64) -> Result<()> {
+        let name_in = self.argument_name(name)?;
+        unsafe { bindings::g_object_set(self.operation as bindings::gpointer, name_in.as_ptr(), value, NULL) };
+        Ok(())
+    }
+
+    fn set_string(&mut self, name: &str, value: &str) -> Result<()> {
+        let name_in = self.argument_name(name)?;
+        let value_in = utils::new_c_string(value)?;
+        unsafe {
+            bindings::g_object_set(self.operation as bindings::gpointer, name_in.as_ptr(), value_in.as_ptr(), NULL)
+        };
+        Ok(())
+    }
+
+    // images, targets and other objects; the operation takes its own reference
+    fn set_pointer(&mut self, name: &str, value: bindings::gpointer) -> Result<()> {
+        let name_in = self.argument_name(name)?;
+        unsafe { bindings::g_object_set(self.operation as bindings::gpointer, name_in.as_ptr(), value, NULL) };
+        Ok(())
+    }
+
+    fn build(&mut self) -> i32 {
+        unsafe { bindings::vips_cache_operation_buildp(&mut self.operation) }
+    }
+
+    // `out` gets its own reference, which outlives the operation
+    fn get_pointer(&self, name: &str, out: *mut bindings::gpointer) -> Result<()> {
+        let name_in = utils::new_c_string(name)?;
+        unsafe { bindings::g_object_get(self.operation as bindings::gpointer, name_in.as_ptr(), out, NULL) };
+        Ok(())
+    }
+}
+
+impl Drop for VipsCall {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::vips_object_unref_outputs(self.operation as *mut bindings::VipsObject);
+            bindings::g_object_unref(self.operation as bindings::gpointer);
+        }
+    }
+}
+
+// gifsave only exists from libvips 8.12 (interframe_maxerror from 8.13), later than the headers these bindings
+// come from, so it is called by name instead of linking against vips_gifsave*. Only the options that differ
+// from their defaults are set, which keeps 8.12 working as long as interframe_maxerror is left at 0
+
+/// VipsForeignSaveCgifFile (gifsave), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// filename: `&str` -> Filename to save to
+pub fn gifsave(inp: &VipsImage, filename: &str) -> Result<()> {
+    gifsave_with_opts(inp, filename, &GifsaveOptions::default())
+}
+
+/// Options for gifsave operation
+#[derive(Clone, Debug)]
+pub struct GifsaveOptions {
+    /// dither: `f64` -> Amount of dithering
+    /// min: 0, max: 1, default: 1
+    pub dither: f64,
+    /// effort: `i32` -> Quantisation effort
+    /// min: 1, max: 10, default: 7
+    pub effort: i32,
+    /// bitdepth: `i32` -> Number of bits per pixel
+    /// min: 1, max: 8, default: 8
+    pub bitdepth: i32,
+    /// interframe_maxerror: `f64` -> Maximum inter-frame error for transparency
+    /// min: 0, max: 32, default: 0
+    pub interframe_maxerror: f64,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for GifsaveOptions {
+    fn default() -> Self {
+        GifsaveOptions {
+            dither: f64::from(1),
+            effort: i32::from(7),
+            bitdepth: i32::from(8),
+            interframe_maxerror: f64::from(0),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveCgifFile (gifsave), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// filename: `&str` -> Filename to save to
+/// gifsave_options: `&GifsaveOptions` -> optional arguments
+pub fn gifsave_with_opts(inp: &VipsImage, filename: &str, gifsave_options: &GifsaveOptions) -> Result<()> {
+    let mut call = VipsCall::new("gifsave", Error::GifsaveError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    call.set_string("filename", filename)?;
+    if gifsave_options.dither != 1.0 {
+        call.set_double("dither", gifsave_options.dither)?;
+    }
+    if gifsave_options.effort != 7 {
+        call.set_int("effort", gifsave_options.effort)?;
+    }
+    if gifsave_options.bitdepth != 8 {
+        call.set_int("bitdepth", gifsave_options.bitdepth)?;
+    }
+    if gifsave_options.interframe_maxerror != 0.0 {
+        call.set_double("interframe_maxerror", gifsave_options.interframe_maxerror)?;
+    }
+    if gifsave_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    utils::result(call.build(), (), Error::GifsaveError)
+}
+
+/// VipsForeignSaveCgifBuffer (gifsave_buffer), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// returns `Vec<u8>` - Buffer to save to
+pub fn gifsave_buffer(inp: &VipsImage) -> Result<Vec<u8>> {
+    gifsave_buffer_with_opts(inp, &GifsaveBufferOptions::default())
+}
+
+/// Options for gifsave_buffer operation
+#[derive(Clone, Debug)]
+pub struct GifsaveBufferOptions {
+    /// dither: `f64` -> Amount of dithering
+    /// min: 0, max: 1, default: 1
+    pub dither: f64,
+    /// effort: `i32` -> Quantisation effort
+    /// min: 1, max: 10, default: 7
+    pub effort: i32,
+    /// bitdepth: `i32` -> Number of bits per pixel
+    /// min: 1, max: 8, default: 8
+    pub bitdepth: i32,
+    /// interframe_maxerror: `f64` -> Maximum inter-frame error for transparency
+    /// min: 0, max: 32, default: 0
+    pub interframe_maxerror: f64,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for GifsaveBufferOptions {
+    fn default() -> Self {
+        GifsaveBufferOptions {
+            dither: f64::from(1),
+            effort: i32::from(7),
+            bitdepth: i32::from(8),
+            interframe_maxerror: f64::from(0),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveCgifBuffer (gifsave_buffer), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// gifsave_buffer_options: `&GifsaveBufferOptions` -> optional arguments
+/// returns `Vec<u8>` - Buffer to save to
+pub fn gifsave_buffer_with_opts(
+    inp: &VipsImage,
+    gifsave_buffer_options: &GifsaveBufferOptions,
+) -> Result<Vec<u8>> {
+    let mut call = VipsCall::new("gifsave_buffer", Error::GifsaveBufferError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    if gifsave_buffer_options.dither != 1.0 {
+        call.set_double("dither", gifsave_buffer_options.dither)?;
+    }
+    if gifsave_buffer_options.effort != 7 {
+        call.set_int("effort", gifsave_buffer_options.effort)?;
+    }
+    if gifsave_buffer_options.bitdepth != 8 {
+        call.set_int("bitdepth", gifsave_buffer_options.bitdepth)?;
+    }
+    if gifsave_buffer_options.interframe_maxerror != 0.0 {
+        call.set_double("interframe_maxerror", gifsave_buffer_options.interframe_maxerror)?;
+    }
+    if gifsave_buffer_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    // there is no blob to read when the operation failed
+    if call.build() != 0 {
+        return Err(Error::GifsaveBufferError)
+    }
+    let mut buffer_out: *mut bindings::VipsBlob = null_mut();
+    call.get_pointer("buffer", &mut buffer_out as *mut _ as *mut bindings::gpointer)?;
+    Ok(VipsBlob { ctx: buffer_out }.into())
+}
+
+/// VipsForeignSaveCgifTarget (gifsave_target), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// target: `&VipsTarget` -> Target to save to
+pub fn gifsave_target(inp: &VipsImage, target: &VipsTarget) -> Result<()> {
+    gifsave_target_with_opts(inp, target, &GifsaveTargetOptions::default())
+}
+
+/// Options for gifsave_target operation
+#[derive(Clone, Debug)]
+pub struct GifsaveTargetOptions {
+    /// dither: `f64` -> Amount of dithering
+    /// min: 0, max: 1, default: 1
+    pub dither: f64,
+    /// effort: `i32` -> Quantisation effort
+    /// min: 1, max: 10, default: 7
+    pub effort: i32,
+    /// bitdepth: `i32` -> Number of bits per pixel
+    /// min: 1, max: 8, default: 8
+    pub bitdepth: i32,
+    /// interframe_maxerror: `f64` -> Maximum inter-frame error for transparency
+    /// min: 0, max: 32, default: 0
+    pub interframe_maxerror: f64,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for GifsaveTargetOptions {
+    fn default() -> Self {
+        GifsaveTargetOptions {
+            dither: f64::from(1),
+            effort: i32::from(7),
+            bitdepth: i32::from(8),
+            interframe_maxerror: f64::from(0),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveCgifTarget (gifsave_target), save as gif (.gif), priority=0, rgba-only
+/// inp: `&VipsImage` -> Image to save
+/// target: `&VipsTarget` -> Target to save to
+/// gifsave_target_options: `&GifsaveTargetOptions` -> optional arguments
+pub fn gifsave_target_with_opts(
+    inp: &VipsImage,
+    target: &VipsTarget,
+    gifsave_target_options: &GifsaveTargetOptions,
+) -> Result<()> {
+    let mut call = VipsCall::new("gifsave_target", Error::GifsaveTargetError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    call.set_pointer("target", target.ctx as bindings::gpointer)?;
+    if gifsave_target_options.dither != 1.0 {
+        call.set_double("dither", gifsave_target_options.dither)?;
+    }
+    if gifsave_target_options.effort != 7 {
+        call.set_int("effort", gifsave_target_options.effort)?;
+    }
+    if gifsave_target_options.bitdepth != 8 {
+        call.set_int("bitdepth", gifsave_target_options.bitdepth)?;
+    }
+    if gifsave_target_options.interframe_maxerror != 0.0 {
+        call.set_double("interframe_maxerror", gifsave_target_options.interframe_maxerror)?;
+    }
+    if gifsave_target_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    utils::result(call.build(), (), Error::GifsaveTargetError)
+}
+
+// JPEG XL arrived in libvips 8.11, later than the headers these bindings come from. vips.h declares
+// vips_jxl* for bindgen, but like gifsave the operations are called by name, so a libvips without them
+// still links and fails at runtime instead
+
+/// VipsForeignLoadJxlFile (jxlload), load JPEG-XL image (.jxl), priority=0, is_a, get_flags, header, load
+/// filename: `&str` -> Filename to load from
+/// returns `VipsImage` - Output image
+pub fn jxlload(filename: &str) -> Result<VipsImage> {
+    jxlload_with_opts(filename, &JxlloadOptions::default())
+}
+
+/// Options for jxlload operation
+#[derive(Clone, Debug)]
+pub struct JxlloadOptions {
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for JxlloadOptions {
+    fn default() -> Self {
+        JxlloadOptions {
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadJxlFile (jxlload), load JPEG-XL image (.jxl), priority=0, is_a, get_flags, header, load
+/// filename: `&str` -> Filename to load from
+/// jxlload_options: `&JxlloadOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn jxlload_with_opts(filename: &str, jxlload_options: &JxlloadOptions) -> Result<VipsImage> {
+    unsafe {
+        let filename_in: CString = utils::new_c_string(filename)?;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+        let operation_name = utils::new_c_string("jxlload")?;
+
+        let flags_in: i32 = jxlload_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if jxlload_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = jxlload_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if jxlload_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_call(
+            operation_name.as_ptr(),
+            filename_in.as_ptr(),
+            &mut out_out,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::JxlloadError,
+        )
+    }
+}
+
+/// VipsForeignLoadJxlBuffer (jxlload_buffer), load JPEG-XL image, priority=0, is_a_buffer, get_flags, header, load
+/// buffer: `&[u8]` -> Buffer to load from
+/// returns `VipsImage` - Output image
+pub fn jxlload_buffer(buffer: &[u8]) -> Result<VipsImage> {
+    jxlload_buffer_with_opts(buffer, &JxlloadBufferOptions::default())
+}
+
+/// Options for jxlload_buffer operation
+#[derive(Clone, Debug)]
+pub struct JxlloadBufferOptions {
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for JxlloadBufferOptions {
+    fn default() -> Self {
+        JxlloadBufferOptions {
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadJxlBuffer (jxlload_buffer), load JPEG-XL image, priority=0, is_a_buffer, get_flags, header, load
+/// buffer: `&[u8]` -> Buffer to load from
+/// jxlload_buffer_options: `&JxlloadBufferOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn jxlload_buffer_with_opts(
+    buffer: &[u8],
+    jxlload_buffer_options: &JxlloadBufferOptions,
+) -> Result<VipsImage> {
+    unsafe {
+        // the loader can keep reading the blob after this returns, so it gets its own copy of `buffer`
+        let buffer_in: *mut bindings::VipsBlob =
+            bindings::vips_blob_copy(buffer.as_ptr() as *const c_void, buffer.len() as u64);
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+        let operation_name = utils::new_c_string("jxlload_buffer")?;
+
+        let flags_in: i32 = jxlload_buffer_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if jxlload_buffer_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = jxlload_buffer_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if jxlload_buffer_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_call(
+            operation_name.as_ptr(),
+            buffer_in,
+            &mut out_out,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        bindings::vips_area_unref(buffer_in as *mut bindings::VipsArea);
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::JxlloadBufferError,
+        )
+    }
+}
+
+/// VipsForeignLoadJxlSource (jxlload_source), load JPEG-XL image, priority=0, is_a_source, get_flags, header, load
+/// source: `&VipsSource` -> Source to load from
+/// returns `VipsImage` - Output image
+pub fn jxlload_source(source: &VipsSource) -> Result<VipsImage> {
+    jxlload_source_with_opts(source, &JxlloadSourceOptions::default())
+}
+
+/// Options for jxlload_source operation
+#[derive(Clone, Debug)]
+pub struct JxlloadSourceOptions {
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for JxlloadSourceOptions {
+    fn default() -> Self {
+        JxlloadSourceOptions {
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadJxlSource (jxlload_source), load JPEG-XL image, priority=0, is_a_source, get_flags, header, load
+/// source: `&VipsSource` -> Source to load from
+/// jxlload_source_options: `&JxlloadSourceOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn jxlload_source_with_opts(
+    source: &VipsSource,
+    jxlload_source_options: &JxlloadSourceOptions,
+) -> Result<VipsImage> {
+    unsafe {
+        let source_in: *mut bindings::VipsSource = source.ctx;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+        let operation_name = utils::new_c_string("jxlload_source")?;
+
+        let flags_in: i32 = jxlload_source_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if jxlload_source_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = jxlload_source_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if jxlload_source_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_call(
+            operation_name.as_ptr(),
+            source_in,
+            &mut out_out,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::JxlloadSourceError,
+        )
+    }
+}
+
+/// VipsForeignSaveJxlFile (jxlsave), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// filename: `&str` -> Filename to save to
+pub fn jxlsave(inp: &VipsImage, filename: &str) -> Result<()> {
+    jxlsave_with_opts(inp, filename, &JxlsaveOptions::default())
+}
+
+/// Options for jxlsave operation
+#[derive(Clone, Debug)]
+pub struct JxlsaveOptions {
+    /// tier: `i32` -> Decode speed tier
+    /// min: 0, max: 4, default: 0
+    pub tier: i32,
+    /// distance: `f64` -> Target butteraugli distance
+    /// min: 0, max: 15, default: 1
+    pub distance: f64,
+    /// effort: `i32` -> Encoding effort
+    /// min: 3, max: 9, default: 7
+    pub effort: i32,
+    /// lossless: `bool` -> Enable lossless compression
+    /// default: false
+    pub lossless: bool,
+    /// q: `i32` -> Quality factor, only used when `distance` is left at its default
+    /// min: 0, max: 100, default: 75
+    pub q: i32,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for JxlsaveOptions {
+    fn default() -> Self {
+        JxlsaveOptions {
+            tier: i32::from(0),
+            distance: f64::from(1),
+            effort: i32::from(7),
+            lossless: false,
+            q: i32::from(75),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveJxlFile (jxlsave), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// filename: `&str` -> Filename to save to
+/// jxlsave_options: `&JxlsaveOptions` -> optional arguments
+pub fn jxlsave_with_opts(
+    inp: &VipsImage,
+    filename: &str,
+    jxlsave_options: &JxlsaveOptions,
+) -> Result<()> {
+    let mut call = VipsCall::new("jxlsave", Error::JxlsaveError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    call.set_string("filename", filename)?;
+    if jxlsave_options.tier != 0 {
+        call.set_int("tier", jxlsave_options.tier)?;
+    }
+    // libvips derives the distance from Q unless the distance itself is set
+    if jxlsave_options.distance != 1.0 {
+        call.set_double("distance", jxlsave_options.distance)?;
+    }
+    if jxlsave_options.effort != 7 {
+        call.set_int("effort", jxlsave_options.effort)?;
+    }
+    if jxlsave_options.lossless {
+        call.set_bool("lossless", true)?;
+    }
+    if jxlsave_options.q != 75 {
+        call.set_int("Q", jxlsave_options.q)?;
+    }
+    if jxlsave_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    utils::result(call.build(), (), Error::JxlsaveError)
+}
+
+/// VipsForeignSaveJxlBuffer (jxlsave_buffer), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// returns `Vec<u8>` - Buffer to save to
+pub fn jxlsave_buffer(inp: &VipsImage) -> Result<Vec<u8>> {
+    jxlsave_buffer_with_opts(inp, &JxlsaveBufferOptions::default())
+}
+
+/// Options for jxlsave_buffer operation
+#[derive(Clone, Debug)]
+pub struct JxlsaveBufferOptions {
+    /// tier: `i32` -> Decode speed tier
+    /// min: 0, max: 4, default: 0
+    pub tier: i32,
+    /// distance: `f64` -> Target butteraugli distance
+    /// min: 0, max: 15, default: 1
+    pub distance: f64,
+    /// effort: `i32` -> Encoding effort
+    /// min: 3, max: 9, default: 7
+    pub effort: i32,
+    /// lossless: `bool` -> Enable lossless compression
+    /// default: false
+    pub lossless: bool,
+    /// q: `i32` -> Quality factor, only used when `distance` is left at its default
+    /// min: 0, max: 100, default: 75
+    pub q: i32,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for JxlsaveBufferOptions {
+    fn default() -> Self {
+        JxlsaveBufferOptions {
+            tier: i32::from(0),
+            distance: f64::from(1),
+            effort: i32::from(7),
+            lossless: false,
+            q: i32::from(75),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveJxlBuffer (jxlsave_buffer), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// jxlsave_buffer_options: `&JxlsaveBufferOptions` -> optional arguments
+/// returns `Vec<u8>` - Buffer to save to
+pub fn jxlsave_buffer_with_opts(
+    inp: &VipsImage,
+    jxlsave_buffer_options: &JxlsaveBufferOptions,
+) -> Result<Vec<u8>> {
+    let mut call = VipsCall::new("jxlsave_buffer", Error::JxlsaveBufferError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    if jxlsave_buffer_options.tier != 0 {
+        call.set_int("tier", jxlsave_buffer_options.tier)?;
+    }
+    // libvips derives the distance from Q unless the distance itself is set
+    if jxlsave_buffer_options.distance != 1.0 {
+        call.set_double("distance", jxlsave_buffer_options.distance)?;
+    }
+    if jxlsave_buffer_options.effort != 7 {
+        call.set_int("effort", jxlsave_buffer_options.effort)?;
+    }
+    if jxlsave_buffer_options.lossless {
+        call.set_bool("lossless", true)?;
+    }
+    if jxlsave_buffer_options.q != 75 {
+        call.set_int("Q", jxlsave_buffer_options.q)?;
+    }
+    if jxlsave_buffer_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    // there is no blob to read when the operation failed
+    if call.build() != 0 {
+        return Err(Error::JxlsaveBufferError)
+    }
+    let mut buffer_out: *mut bindings::VipsBlob = null_mut();
+    call.get_pointer("buffer", &mut buffer_out as *mut _ as *mut bindings::gpointer)?;
+    Ok(VipsBlob { ctx: buffer_out }.into())
+}
+
+/// VipsForeignSaveJxlTarget (jxlsave_target), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// target: `&VipsTarget` -> Target to save to
+pub fn jxlsave_target(inp: &VipsImage, target: &VipsTarget) -> Result<()> {
+    jxlsave_target_with_opts(inp, target, &JxlsaveTargetOptions::default())
+}
+
+/// Options for jxlsave_target operation
+#[derive(Clone, Debug)]
+pub struct JxlsaveTargetOptions {
+    /// tier: `i32` -> Decode speed tier
+    /// min: 0, max: 4, default: 0
+    pub tier: i32,
+    /// distance: `f64` -> Target butteraugli distance
+    /// min: 0, max: 15, default: 1
+    pub distance: f64,
+    /// effort: `i32` -> Encoding effort
+    /// min: 3, max: 9, default: 7
+    pub effort: i32,
+    /// lossless: `bool` -> Enable lossless compression
+    /// default: false
+    pub lossless: bool,
+    /// q: `i32` -> Quality factor, only used when `distance` is left at its default
+    /// min: 0, max: 100, default: 75
+    pub q: i32,
+    /// strip: `bool` -> Strip all metadata from image
+    /// default: false
+    pub strip: bool,
+}
+
+impl std::default::Default for JxlsaveTargetOptions {
+    fn default() -> Self {
+        JxlsaveTargetOptions {
+            tier: i32::from(0),
+            distance: f64::from(1),
+            effort: i32::from(7),
+            lossless: false,
+            q: i32::from(75),
+            strip: false,
+        }
+    }
+}
+
+/// VipsForeignSaveJxlTarget (jxlsave_target), save image in JPEG-XL format (.jxl), priority=0, untiled
+/// inp: `&VipsImage` -> Image to save
+/// target: `&VipsTarget` -> Target to save to
+/// jxlsave_target_options: `&JxlsaveTargetOptions` -> optional arguments
+pub fn jxlsave_target_with_opts(
+    inp: &VipsImage,
+    target: &VipsTarget,
+    jxlsave_target_options: &JxlsaveTargetOptions,
+) -> Result<()> {
+    let mut call = VipsCall::new("jxlsave_target", Error::JxlsaveTargetError)?;
+    call.set_pointer("in", inp.ctx as bindings::gpointer)?;
+    call.set_pointer("target", target.ctx as bindings::gpointer)?;
+    if jxlsave_target_options.tier != 0 {
+        call.set_int("tier", jxlsave_target_options.tier)?;
+    }
+    // libvips derives the distance from Q unless the distance itself is set
+    if jxlsave_target_options.distance != 1.0 {
+        call.set_double("distance", jxlsave_target_options.distance)?;
+    }
+    if jxlsave_target_options.effort != 7 {
+        call.set_int("effort", jxlsave_target_options.effort)?;
+    }
+    if jxlsave_target_options.lossless {
+        call.set_bool("lossless", true)?;
+    }
+    if jxlsave_target_options.q != 75 {
+        call.set_int("Q", jxlsave_target_options.q)?;
+    }
+    if jxlsave_target_options.strip {
+        call.set_bool("strip", true)?;
+    }
+    utils::result(call.build(), (), Error::JxlsaveTargetError)
+}
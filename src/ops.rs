@@ -11516,6 +11516,437 @@ pub fn heifload_source_with_opts(
     }
 }
 
+/// VipsForeignLoadPdfFile (pdfload), load PDF with libpoppler (.pdf), priority=0, is_a, get_flags, get_flags_filename, header, load
+/// filename: `&str` -> Filename to load from
+/// returns `VipsImage` - Output image
+pub fn pdfload(filename: &str) -> Result<VipsImage> {
+    unsafe {
+        let filename_in: CString = utils::new_c_string(filename)?;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let vips_op_response = bindings::vips_pdfload(filename_in.as_ptr(), &mut out_out, NULL);
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadError,
+        )
+    }
+}
+
+/// Options for pdfload operation
+#[derive(Clone, Debug)]
+pub struct PdfloadOptions {
+    /// page: `i32` -> Load this page from the file
+    /// min: 0, max: 100000, default: 0
+    pub page: i32,
+    /// n: `i32` -> Load this many pages
+    /// min: -1, max: 100000, default: 1
+    pub n: i32,
+    /// dpi: `f64` -> Render at this DPI
+    /// min: 0.001, max: 100000, default: 72
+    pub dpi: f64,
+    /// scale: `f64` -> Scale output by this factor
+    /// min: 0.001, max: 100000, default: 1
+    pub scale: f64,
+    /// background: `Vec<f64>` -> Background value
+    /// default: [255.0]
+    pub background: Vec<f64>,
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for PdfloadOptions {
+    fn default() -> Self {
+        PdfloadOptions {
+            page: i32::from(0),
+            n: i32::from(1),
+            dpi: f64::from(72),
+            scale: f64::from(1),
+            // an empty background would leave libvips without a colour to render the page on
+            background: vec![255.0],
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadPdfFile (pdfload), load PDF with libpoppler (.pdf), priority=0, is_a, get_flags, get_flags_filename, header, load
+/// filename: `&str` -> Filename to load from
+/// pdfload_options: `&PdfloadOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn pdfload_with_opts(filename: &str, pdfload_options: &PdfloadOptions) -> Result<VipsImage> {
+    unsafe {
+        let filename_in: CString = utils::new_c_string(filename)?;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let page_in: i32 = pdfload_options.page;
+        let page_in_name = utils::new_c_string("page")?;
+
+        let n_in: i32 = pdfload_options.n;
+        let n_in_name = utils::new_c_string("n")?;
+
+        let dpi_in: f64 = pdfload_options.dpi;
+        let dpi_in_name = utils::new_c_string("dpi")?;
+
+        let scale_in: f64 = pdfload_options.scale;
+        let scale_in_name = utils::new_c_string("scale")?;
+
+        let background_wrapper = utils::VipsArrayDoubleWrapper::from(&pdfload_options.background[..]);
+        let background_in = background_wrapper.ctx;
+        let background_in_name = utils::new_c_string("background")?;
+
+        let flags_in: i32 = pdfload_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if pdfload_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = pdfload_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if pdfload_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_pdfload(
+            filename_in.as_ptr(),
+            &mut out_out,
+            page_in_name.as_ptr(),
+            page_in,
+            n_in_name.as_ptr(),
+            n_in,
+            dpi_in_name.as_ptr(),
+            dpi_in,
+            scale_in_name.as_ptr(),
+            scale_in,
+            background_in_name.as_ptr(),
+            background_in,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadError,
+        )
+    }
+}
+
+/// VipsForeignLoadPdfBuffer (pdfload_buffer), load PDF with libpoppler, priority=0, is_a_buffer, get_flags, get_flags_filename, header, load
+/// buffer: `&[u8]` -> Buffer to load from
+/// returns `VipsImage` - Output image
+pub fn pdfload_buffer(buffer: &[u8]) -> Result<VipsImage> {
+    unsafe {
+        let buffer_in: *mut c_void = buffer.as_ptr() as *mut c_void;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let vips_op_response =
+            bindings::vips_pdfload_buffer(buffer_in, buffer.len() as u64, &mut out_out, NULL);
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadBufferError,
+        )
+    }
+}
+
+/// Options for pdfload_buffer operation
+#[derive(Clone, Debug)]
+pub struct PdfloadBufferOptions {
+    /// page: `i32` -> Load this page from the file
+    /// min: 0, max: 100000, default: 0
+    pub page: i32,
+    /// n: `i32` -> Load this many pages
+    /// min: -1, max: 100000, default: 1
+    pub n: i32,
+    /// dpi: `f64` -> Render at this DPI
+    /// min: 0.001, max: 100000, default: 72
+    pub dpi: f64,
+    /// scale: `f64` -> Scale output by this factor
+    /// min: 0.001, max: 100000, default: 1
+    pub scale: f64,
+    /// background: `Vec<f64>` -> Background value
+    /// default: [255.0]
+    pub background: Vec<f64>,
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for PdfloadBufferOptions {
+    fn default() -> Self {
+        PdfloadBufferOptions {
+            page: i32::from(0),
+            n: i32::from(1),
+            dpi: f64::from(72),
+            scale: f64::from(1),
+            // an empty background would leave libvips without a colour to render the page on
+            background: vec![255.0],
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadPdfBuffer (pdfload_buffer), load PDF with libpoppler, priority=0, is_a_buffer, get_flags, get_flags_filename, header, load
+/// buffer: `&[u8]` -> Buffer to load from
+/// pdfload_buffer_options: `&PdfloadBufferOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn pdfload_buffer_with_opts(
+    buffer: &[u8],
+    pdfload_buffer_options: &PdfloadBufferOptions,
+) -> Result<VipsImage> {
+    unsafe {
+        let buffer_in: *mut c_void = buffer.as_ptr() as *mut c_void;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let page_in: i32 = pdfload_buffer_options.page;
+        let page_in_name = utils::new_c_string("page")?;
+
+        let n_in: i32 = pdfload_buffer_options.n;
+        let n_in_name = utils::new_c_string("n")?;
+
+        let dpi_in: f64 = pdfload_buffer_options.dpi;
+        let dpi_in_name = utils::new_c_string("dpi")?;
+
+        let scale_in: f64 = pdfload_buffer_options.scale;
+        let scale_in_name = utils::new_c_string("scale")?;
+
+        let background_wrapper = utils::VipsArrayDoubleWrapper::from(&pdfload_buffer_options.background[..]);
+        let background_in = background_wrapper.ctx;
+        let background_in_name = utils::new_c_string("background")?;
+
+        let flags_in: i32 = pdfload_buffer_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if pdfload_buffer_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = pdfload_buffer_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if pdfload_buffer_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_pdfload_buffer(
+            buffer_in,
+            buffer.len() as u64,
+            &mut out_out,
+            page_in_name.as_ptr(),
+            page_in,
+            n_in_name.as_ptr(),
+            n_in,
+            dpi_in_name.as_ptr(),
+            dpi_in,
+            scale_in_name.as_ptr(),
+            scale_in,
+            background_in_name.as_ptr(),
+            background_in,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadBufferError,
+        )
+    }
+}
+
+/// VipsForeignLoadPdfSource (pdfload_source), load PDF from source, priority=0, is_a_source, get_flags, get_flags_filename, header, load
+/// source: `&VipsSource` -> Source to load from
+/// returns `VipsImage` - Output image
+pub fn pdfload_source(source: &VipsSource) -> Result<VipsImage> {
+    unsafe {
+        let source_in: *mut bindings::VipsSource = source.ctx;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let vips_op_response = bindings::vips_pdfload_source(source_in, &mut out_out, NULL);
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadSourceError,
+        )
+    }
+}
+
+/// Options for pdfload_source operation
+#[derive(Clone, Debug)]
+pub struct PdfloadSourceOptions {
+    /// page: `i32` -> Load this page from the file
+    /// min: 0, max: 100000, default: 0
+    pub page: i32,
+    /// n: `i32` -> Load this many pages
+    /// min: -1, max: 100000, default: 1
+    pub n: i32,
+    /// dpi: `f64` -> Render at this DPI
+    /// min: 0.001, max: 100000, default: 72
+    pub dpi: f64,
+    /// scale: `f64` -> Scale output by this factor
+    /// min: 0.001, max: 100000, default: 1
+    pub scale: f64,
+    /// background: `Vec<f64>` -> Background value
+    /// default: [255.0]
+    pub background: Vec<f64>,
+    /// flags: `ForeignFlags` -> Flags for this file
+    ///  `None` -> VIPS_FOREIGN_NONE = 0 [DEFAULT]
+    ///  `Partial` -> VIPS_FOREIGN_PARTIAL = 1
+    ///  `Bigendian` -> VIPS_FOREIGN_BIGENDIAN = 2
+    ///  `Sequential` -> VIPS_FOREIGN_SEQUENTIAL = 4
+    ///  `All` -> VIPS_FOREIGN_ALL = 7
+    pub flags: ForeignFlags,
+    /// memory: `bool` -> Force open via memory
+    /// default: false
+    pub memory: bool,
+    /// access: `Access` -> Required access pattern for this file
+    ///  `Random` -> VIPS_ACCESS_RANDOM = 0 [DEFAULT]
+    ///  `Sequential` -> VIPS_ACCESS_SEQUENTIAL = 1
+    ///  `SequentialUnbuffered` -> VIPS_ACCESS_SEQUENTIAL_UNBUFFERED = 2
+    ///  `Last` -> VIPS_ACCESS_LAST = 3
+    pub access: Access,
+    /// fail: `bool` -> Fail on first error
+    /// default: false
+    pub fail: bool,
+}
+
+impl std::default::Default for PdfloadSourceOptions {
+    fn default() -> Self {
+        PdfloadSourceOptions {
+            page: i32::from(0),
+            n: i32::from(1),
+            dpi: f64::from(72),
+            scale: f64::from(1),
+            // an empty background would leave libvips without a colour to render the page on
+            background: vec![255.0],
+            flags: ForeignFlags::None,
+            memory: false,
+            access: Access::Random,
+            fail: false,
+        }
+    }
+}
+
+/// VipsForeignLoadPdfSource (pdfload_source), load PDF from source, priority=0, is_a_source, get_flags, get_flags_filename, header, load
+/// source: `&VipsSource` -> Source to load from
+/// pdfload_source_options: `&PdfloadSourceOptions` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn pdfload_source_with_opts(
+    source: &VipsSource,
+    pdfload_source_options: &PdfloadSourceOptions,
+) -> Result<VipsImage> {
+    unsafe {
+        let source_in: *mut bindings::VipsSource = source.ctx;
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+
+        let page_in: i32 = pdfload_source_options.page;
+        let page_in_name = utils::new_c_string("page")?;
+
+        let n_in: i32 = pdfload_source_options.n;
+        let n_in_name = utils::new_c_string("n")?;
+
+        let dpi_in: f64 = pdfload_source_options.dpi;
+        let dpi_in_name = utils::new_c_string("dpi")?;
+
+        let scale_in: f64 = pdfload_source_options.scale;
+        let scale_in_name = utils::new_c_string("scale")?;
+
+        let background_wrapper = utils::VipsArrayDoubleWrapper::from(&pdfload_source_options.background[..]);
+        let background_in = background_wrapper.ctx;
+        let background_in_name = utils::new_c_string("background")?;
+
+        let flags_in: i32 = pdfload_source_options.flags as i32;
+        let flags_in_name = utils::new_c_string("flags")?;
+
+        let memory_in: i32 = if pdfload_source_options.memory { 1 } else { 0 };
+        let memory_in_name = utils::new_c_string("memory")?;
+
+        let access_in: i32 = pdfload_source_options.access as i32;
+        let access_in_name = utils::new_c_string("access")?;
+
+        let fail_in: i32 = if pdfload_source_options.fail { 1 } else { 0 };
+        let fail_in_name = utils::new_c_string("fail")?;
+
+        let vips_op_response = bindings::vips_pdfload_source(
+            source_in,
+            &mut out_out,
+            page_in_name.as_ptr(),
+            page_in,
+            n_in_name.as_ptr(),
+            n_in,
+            dpi_in_name.as_ptr(),
+            dpi_in,
+            scale_in_name.as_ptr(),
+            scale_in,
+            background_in_name.as_ptr(),
+            background_in,
+            flags_in_name.as_ptr(),
+            flags_in,
+            memory_in_name.as_ptr(),
+            memory_in,
+            access_in_name.as_ptr(),
+            access_in,
+            fail_in_name.as_ptr(),
+            fail_in,
+            NULL,
+        );
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::PdfloadSourceError,
+        )
+    }
+}
+
 /// VipsForeignSaveCsvFile (csvsave), save image to csv (.csv), priority=0, mono
 /// inp: `&VipsImage` -> Image to save
 /// filename: `&str` -> Filename to save to
@@ -21083,15 +21514,37 @@ pub fn globalbalance_with_opts(
     }
 }
 
-//pub fn image_get_string(input: &VipsImage, name: &str) -> Result<String> {
-pub fn image_get_string(_input: &VipsImage, _name: &str) -> String {
-   //unsafe {
-       //Ok(String::from(""))
-        //let input_in: *mut bindings::VipsImage = input.ctx;
-        //let name_in: CString = utils::new_c_string(name)?;
-        //let mut out: *const   
-    String::from("")
-   //}  
+/// reads a string field from the image header
+/// input: `&VipsImage` -> Input image
+/// name: `&str` -> Field name, e.g. `exif-ifd0-Make`
+/// returns `String` - Field value, or an empty string when the field is missing or isn't a string.
+/// Use `VipsImage::get_string` to tell those cases apart
+pub fn image_get_string(input: &VipsImage, name: &str) -> String {
+    input.get_string(name).unwrap_or_default()
+}
+
+/// reads an integer field from the image header
+/// input: `&VipsImage` -> Input image
+/// name: `&str` -> Field name, e.g. `orientation`
+/// returns `i32` - Field value
+pub fn image_get_int(input: &VipsImage, name: &str) -> Result<i32> {
+    input.get_int(name)
+}
+
+/// reads a double field from the image header
+/// input: `&VipsImage` -> Input image
+/// name: `&str` -> Field name
+/// returns `f64` - Field value
+pub fn image_get_double(input: &VipsImage, name: &str) -> Result<f64> {
+    input.get_double(name)
+}
+
+/// reads a blob field from the image header
+/// input: `&VipsImage` -> Input image
+/// name: `&str` -> Field name, e.g. `icc-profile-data`
+/// returns `Vec<u8>` - Copy of the field bytes
+pub fn image_get_blob(input: &VipsImage, name: &str) -> Result<Vec<u8>> {
+    input.get_blob(name)
 }
 
 pub fn thumbnail_buffer_with_opts1(
@@ -21099,64 +21552,111 @@ pub fn thumbnail_buffer_with_opts1(
     width: i32,
     height: i32,
     _type: &str,
+) -> Result<VipsImage> {
+    let option_string = if _type == "gif" || _type == "webp" {
+        "n=-1"
+    } else {
+        ""
+    };
+    let options = ThumbnailBufferOptions2 {
+        height,
+        option_string: option_string.to_string(),
+        ..ThumbnailBufferOptions2::default()
+    };
+    thumbnail_buffer_with_opts2(buffer, width, &options)
+}
+
+/// Options for thumbnail_buffer_with_opts2. Same as `ThumbnailBufferOptions`, minus the import/export profiles:
+/// libvips tries to load those even when they are empty strings
+#[derive(Clone, Debug)]
+pub struct ThumbnailBufferOptions2 {
+    /// option_string: `String` -> Options that are passed on to the underlying loader
+    pub option_string: String,
+    /// height: `i32` -> Size to this height. 0 means the same as width
+    /// min: 0, max: 10000000, default: 0
+    pub height: i32,
+    /// size: `Size` -> Only upsize, only downsize, or both
+    pub size: Size,
+    /// no_rotate: `bool` -> Don't use orientation tags to rotate image upright
+    /// default: false
+    pub no_rotate: bool,
+    /// crop: `Interesting` -> Reduce to fill target rectangle, then crop
+    pub crop: Interesting,
+    /// linear: `bool` -> Reduce in linear light
+    /// default: false
+    pub linear: bool,
+}
+
+impl std::default::Default for ThumbnailBufferOptions2 {
+    fn default() -> Self {
+        ThumbnailBufferOptions2 {
+            option_string: String::new(),
+            height: 0,
+            size: Size::Both,
+            no_rotate: false,
+            crop: Interesting::None,
+            linear: false,
+        }
+    }
+}
+
+/// VipsThumbnailBuffer (thumbnail_buffer), generate thumbnail from buffer
+/// buffer: `&[u8]` -> Buffer to load from
+/// width: `i32` -> Size to this width
+/// thumbnail_buffer_options: `&ThumbnailBufferOptions2` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn thumbnail_buffer_with_opts2(
+    buffer: &[u8],
+    width: i32,
+    thumbnail_buffer_options: &ThumbnailBufferOptions2,
 ) -> Result<VipsImage> {
     unsafe {
         let buffer_in: *mut c_void = buffer.as_ptr() as *mut c_void;
         let width_in: i32 = width;
         let mut out_out: *mut bindings::VipsImage = null_mut();
 
-        let height_in_name = utils::new_c_string("height")?;
-        let height_in: i32 = height;
-
+        let option_string_in: CString =
+            utils::new_c_string(&thumbnail_buffer_options.option_string)?;
         let option_string_in_name = utils::new_c_string("option-string")?;
-        let option_string_in = utils::new_c_string("n=-1")?;
-
-        let vips_op_response;
-        if _type == "gif" || _type == "webp" {
-            if height == 0 {
-                vips_op_response = bindings::vips_thumbnail_buffer(
-                    buffer_in,
-                    buffer.len() as u64,
-                    &mut out_out,
-                    width_in,
-                    option_string_in_name.as_ptr(),
-                    option_string_in.as_ptr(),
-                    NULL,
-                );
-            } else {
-                vips_op_response = bindings::vips_thumbnail_buffer(
-                    buffer_in,
-                    buffer.len() as u64,
-                    &mut out_out,
-                    width_in,
-                    height_in_name.as_ptr(),
-                    height_in,
-                    option_string_in_name.as_ptr(),
-                    option_string_in.as_ptr(),
-                    NULL,
-                );
-            } 
+
+        let height_in: i32 = if thumbnail_buffer_options.height == 0 {
+            width
         } else {
-            if height == 0 {
-                vips_op_response = bindings::vips_thumbnail_buffer(
-                    buffer_in,
-                    buffer.len() as u64,
-                    &mut out_out,
-                    width_in,
-                    NULL,
-                );
-            } else {
-                vips_op_response = bindings::vips_thumbnail_buffer(
-                    buffer_in,
-                    buffer.len() as u64,
-                    &mut out_out,
-                    width_in,
-                    height_in_name.as_ptr(),
-                    height_in,
-                    NULL,
-                );
-            } 
-        }
+            thumbnail_buffer_options.height
+        };
+        let height_in_name = utils::new_c_string("height")?;
+
+        let size_in: i32 = thumbnail_buffer_options.size as i32;
+        let size_in_name = utils::new_c_string("size")?;
+
+        let no_rotate_in: i32 = if thumbnail_buffer_options.no_rotate { 1 } else { 0 };
+        let no_rotate_in_name = utils::new_c_string("no-rotate")?;
+
+        let crop_in: i32 = thumbnail_buffer_options.crop as i32;
+        let crop_in_name = utils::new_c_string("crop")?;
+
+        let linear_in: i32 = if thumbnail_buffer_options.linear { 1 } else { 0 };
+        let linear_in_name = utils::new_c_string("linear")?;
+
+        let vips_op_response = bindings::vips_thumbnail_buffer(
+            buffer_in,
+            buffer.len() as u64,
+            &mut out_out,
+            width_in,
+            option_string_in_name.as_ptr(),
+            option_string_in.as_ptr(),
+            height_in_name.as_ptr(),
+            height_in,
+            size_in_name.as_ptr(),
+            size_in,
+            no_rotate_in_name.as_ptr(),
+            no_rotate_in,
+            crop_in_name.as_ptr(),
+            crop_in,
+            linear_in_name.as_ptr(),
+            linear_in,
+            NULL,
+        );
         utils::result(
             vips_op_response,
             VipsImage { ctx: out_out },
@@ -21165,18 +21665,196 @@ pub fn thumbnail_buffer_with_opts1(
     }
 }
 
-pub fn text_with_opts1(text: &str, dpi: i32, color: &str) -> Result<VipsImage> {
+/// VipsCopy (copy), copy an image, changing only its interpretation.
+/// `copy_with_opts` can't be used for this as it always sets every option
+/// inp: `&VipsImage` -> Input image
+/// interpretation: `Interpretation` -> Pixel interpretation
+/// returns `VipsImage` - Output image
+pub fn copy_with_interpretation(inp: &VipsImage, interpretation: Interpretation) -> Result<VipsImage> {
     unsafe {
+        let inp_in: *mut bindings::VipsImage = inp.ctx;
         let mut out_out: *mut bindings::VipsImage = null_mut();
-        let text = format!("<span foreground='{}'>{}</span>", color, text);
-        let text_in: CString = utils::new_c_string(&text)?;
-        let dpi_in_name = utils::new_c_string("spacing")?;
 
-        let vips_op_response = bindings::vips_text(&mut out_out, text_in.as_ptr(), dpi_in_name.as_ptr(), dpi, NULL);
+        let interpretation_in: i32 = interpretation as i32;
+        let interpretation_in_name = utils::new_c_string("interpretation")?;
+
+        let vips_op_response = bindings::vips_copy(
+            inp_in,
+            &mut out_out,
+            interpretation_in_name.as_ptr(),
+            interpretation_in,
+            NULL,
+        );
         utils::result(
             vips_op_response,
             VipsImage { ctx: out_out },
-            Error::TextError,
+            Error::CopyError,
         )
     }
 }
+
+/// VipsText (text), make a text image rendered at `dpi`: an sRGB image in `color` (`#RRGGBB` or `#RGB`)
+/// with the text as alpha. Use `text_with_style` for a 1-band mask
+pub fn text_with_opts1(text: &str, dpi: i32, color: &str) -> Result<VipsImage> {
+    let text_style = TextStyle {
+        dpi,
+        rgba: true,
+        color: color.to_string(),
+        ..TextStyle::default()
+    };
+    text_with_style(text, &text_style)
+}
+
+/// Options for text_with_style. Covers the inputs of `TextOptions`; empty strings and a zero `width` or `height` are left unset,
+/// as libvips rejects an empty font file and a height without a width
+#[derive(Clone, Debug)]
+pub struct TextStyle {
+    /// font: `String` -> Pango font description to render with, like `sans bold 12`
+    pub font: String,
+    /// fontfile: `String` -> Load this font file
+    pub fontfile: String,
+    /// width: `i32` -> Maximum image width in pixels. Text wraps at this width
+    /// min: 0, max: 10000000, default: 0
+    pub width: i32,
+    /// height: `i32` -> Maximum image height in pixels. Together with `width`, the dpi is picked so the text fills the box
+    /// min: 0, max: 10000000, default: 0
+    pub height: i32,
+    /// align: `Align` -> Align on the low, centre or high edge
+    ///  `Low` -> VIPS_ALIGN_LOW = 0 [DEFAULT]
+    ///  `Centre` -> VIPS_ALIGN_CENTRE = 1
+    ///  `High` -> VIPS_ALIGN_HIGH = 2
+    pub align: Align,
+    /// justify: `bool` -> Justify lines
+    /// default: false
+    pub justify: bool,
+    /// dpi: `i32` -> DPI to render at
+    /// min: 1, max: 1000000, default: 72
+    pub dpi: i32,
+    /// spacing: `i32` -> Line spacing
+    /// min: 0, max: 1000000, default: 0
+    pub spacing: i32,
+    /// rgba: `bool` -> Output an sRGB image in `color` with the text as alpha, instead of a 1-band mask.
+    /// libvips 8.10 has no `rgba` option, so the colour is applied after rendering
+    /// default: false
+    pub rgba: bool,
+    /// color: `String` -> `#RRGGBB` or `#RGB` text colour, used when `rgba` is set
+    /// default: #000000
+    pub color: String,
+}
+
+impl std::default::Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            font: String::new(),
+            fontfile: String::new(),
+            width: 0,
+            height: 0,
+            align: Align::Low,
+            justify: false,
+            dpi: 72,
+            spacing: 0,
+            rgba: false,
+            color: String::from("#000000"),
+        }
+    }
+}
+
+/// VipsText (text), make a text image. `text` is plain text and is escaped before it is handed to Pango
+/// text: `&str` -> Text to render
+/// text_style: `&TextStyle` -> optional arguments
+/// returns `VipsImage` - Output image
+pub fn text_with_style(text: &str, text_style: &TextStyle) -> Result<VipsImage> {
+    if text_style.height > 0 && text_style.width <= 0 {
+        return Err(Error::OperationError("Text height needs a width to fit into"));
+    }
+    // the operation is built up one option at a time, so the unset ones are never passed
+    let mask = unsafe {
+        let operation_name = utils::new_c_string("text")?;
+        let mut operation = bindings::vips_operation_new(operation_name.as_ptr());
+        if operation.is_null() {
+            return Err(Error::TextError);
+        }
+        let object = operation as bindings::gpointer;
+
+        let text_in: CString = utils::new_c_string(&escape_markup(text))?;
+        let text_in_name = utils::new_c_string("text")?;
+        bindings::g_object_set(object, text_in_name.as_ptr(), text_in.as_ptr(), NULL);
+
+        if !text_style.font.is_empty() {
+            let font_in: CString = utils::new_c_string(&text_style.font)?;
+            let font_in_name = utils::new_c_string("font")?;
+            bindings::g_object_set(object, font_in_name.as_ptr(), font_in.as_ptr(), NULL);
+        }
+
+        if !text_style.fontfile.is_empty() {
+            let fontfile_in: CString = utils::new_c_string(&text_style.fontfile)?;
+            let fontfile_in_name = utils::new_c_string("fontfile")?;
+            bindings::g_object_set(object, fontfile_in_name.as_ptr(), fontfile_in.as_ptr(), NULL);
+        }
+
+        if text_style.width > 0 {
+            let width_in: i32 = text_style.width;
+            let width_in_name = utils::new_c_string("width")?;
+            bindings::g_object_set(object, width_in_name.as_ptr(), width_in, NULL);
+        }
+
+        if text_style.height > 0 {
+            let height_in: i32 = text_style.height;
+            let height_in_name = utils::new_c_string("height")?;
+            bindings::g_object_set(object, height_in_name.as_ptr(), height_in, NULL);
+        }
+
+        let align_in: i32 = text_style.align as i32;
+        let align_in_name = utils::new_c_string("align")?;
+        bindings::g_object_set(object, align_in_name.as_ptr(), align_in, NULL);
+
+        let justify_in: i32 = if text_style.justify { 1 } else { 0 };
+        let justify_in_name = utils::new_c_string("justify")?;
+        bindings::g_object_set(object, justify_in_name.as_ptr(), justify_in, NULL);
+
+        let dpi_in: i32 = text_style.dpi;
+        let dpi_in_name = utils::new_c_string("dpi")?;
+        bindings::g_object_set(object, dpi_in_name.as_ptr(), dpi_in, NULL);
+
+        let spacing_in: i32 = text_style.spacing;
+        let spacing_in_name = utils::new_c_string("spacing")?;
+        bindings::g_object_set(object, spacing_in_name.as_ptr(), spacing_in, NULL);
+
+        let mut out_out: *mut bindings::VipsImage = null_mut();
+        let out_out_name = utils::new_c_string("out")?;
+        let vips_op_response = bindings::vips_cache_operation_buildp(&mut operation);
+        if vips_op_response == 0 {
+            bindings::g_object_get(operation as bindings::gpointer, out_out_name.as_ptr(), &mut out_out, NULL);
+        }
+        // `out` now holds its own reference, the operation's ones can go
+        bindings::vips_object_unref_outputs(operation as *mut bindings::VipsObject);
+        bindings::g_object_unref(operation as bindings::gpointer);
+        utils::result(
+            vips_op_response,
+            VipsImage { ctx: out_out },
+            Error::TextError,
+        )?
+    };
+    if !text_style.rgba {
+        return Ok(mask);
+    }
+    let colour = VipsImage::new_from_image(&mask, &utils::parse_hex_colour(&text_style.color)?)?;
+    let image = bandjoin(&mut [colour, mask])?;
+    copy_with_interpretation(&image, Interpretation::Srgb)
+}
+
+/// escapes `&`, `<`, `>`, `'` and `"` so `text` is shown as-is by Pango
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\'' => escaped.push_str("&apos;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,272 @@
+// (c) Copyright 2019-2020 OLX
+// transformations described by thumbor/imgproxy style query strings, e.g. `w=300&h=200&fit=cover&fmt=webp&q=80`.
+// Parsing validates every value up front, so a spec that parses only fails at `apply` on the image itself.
+use crate::action;
+use crate::action::{ActionOptions, EncodeOptions, Fit, Gravity, ImageFormat, ResizeOptions, RotateOptions, StripPolicy};
+use crate::error::Error;
+use crate::Result;
+
+/// largest width or height a spec may ask for
+pub const MAX_DIMENSION: i32 = 10000;
+
+/// Why a query string was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecError {
+    /// the key isn't one of the supported parameters
+    UnknownKey(String),
+    /// the key (or one of its aliases) appears more than once
+    DuplicateKey(String),
+    /// the value can't be read as what the key expects
+    InvalidValue { key: String, value: String },
+    /// the value is a number outside of `min..=max`
+    OutOfRange {
+        key: String,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+    /// every fit but `inside` needs both a width and a height
+    MissingDimension(Fit),
+}
+
+impl std::fmt::Display for SpecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecError::UnknownKey(key) => write!(f, "unknown parameter `{}`", key),
+            SpecError::DuplicateKey(key) => write!(f, "parameter `{}` is given more than once", key),
+            SpecError::InvalidValue { key, value } => {
+                write!(f, "invalid value `{}` for parameter `{}`", value, key)
+            }
+            SpecError::OutOfRange {
+                key,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "value {} for parameter `{}` is out of range {}..={}",
+                value, key, min, max
+            ),
+            SpecError::MissingDimension(fit) => {
+                write!(f, "fit {:?} needs both a width and a height", fit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+/// A validated transformation. Fields left as `None` leave that part of the image as it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformSpec {
+    /// `w` or `width`, 1 to `MAX_DIMENSION`
+    pub width: Option<i32>,
+    /// `h` or `height`, 1 to `MAX_DIMENSION`
+    pub height: Option<i32>,
+    /// `fit`: `inside`, `outside`, `cover`, `contain` or `fill`. Default: inside
+    pub fit: Fit,
+    /// `g` or `gravity`: `centre`/`center`, `north`, `ne`, `east`, ... or `entropy`/`smart` and `attention`.
+    /// Default: centre
+    pub gravity: Gravity,
+    /// `rot` or `rotate`: 0, 90, 180 or 270, clockwise
+    pub rotate: Option<i32>,
+    /// `fmt` or `format`: output format, like `webp` or `jpg`, that this libvips build can write. Default: the input format
+    pub format: Option<ImageFormat>,
+    /// `q` or `quality`: 1 to 100
+    pub quality: Option<i32>,
+    /// `strip`: `1`/`true` removes all metadata but the colour profile
+    pub strip: bool,
+}
+
+impl std::default::Default for TransformSpec {
+    fn default() -> Self {
+        TransformSpec {
+            width: None,
+            height: None,
+            fit: Fit::Inside,
+            gravity: Gravity::Centre,
+            rotate: None,
+            format: None,
+            quality: None,
+            strip: false,
+        }
+    }
+}
+
+impl TransformSpec {
+    /// parses a query string, with or without the leading `?`. Values may be percent-encoded.
+    /// libvips must be initialised, since `format` is checked against the savers it has
+    pub fn from_query(query: &str) -> std::result::Result<TransformSpec, SpecError> {
+        let mut spec = TransformSpec::default();
+        let mut seen: Vec<&'static str> = Vec::new();
+        for pair in query.trim_start_matches('?').split('&') {
+            if pair.is_empty() {
+                continue;
+            }
+            let mut split = pair.splitn(2, '=');
+            let raw_key = percent_decode(split.next().unwrap_or_default());
+            let value = percent_decode(split.next().unwrap_or_default());
+            let key = match raw_key.as_str() {
+                "w" | "width" => "width",
+                "h" | "height" => "height",
+                "fit" => "fit",
+                "g" | "gravity" => "gravity",
+                "rot" | "rotate" => "rotate",
+                "fmt" | "format" => "format",
+                "q" | "quality" => "quality",
+                "strip" => "strip",
+                _ => return Err(SpecError::UnknownKey(raw_key)),
+            };
+            if seen.contains(&key) {
+                return Err(SpecError::DuplicateKey(raw_key));
+            }
+            seen.push(key);
+
+            let invalid = || SpecError::InvalidValue {
+                key: raw_key.clone(),
+                value: value.clone(),
+            };
+            match key {
+                "width" => spec.width = Some(parse_int(&raw_key, &value, 1, MAX_DIMENSION)?),
+                "height" => spec.height = Some(parse_int(&raw_key, &value, 1, MAX_DIMENSION)?),
+                "quality" => spec.quality = Some(parse_int(&raw_key, &value, 1, 100)?),
+                "rotate" => {
+                    let angle = parse_int(&raw_key, &value, 0, 359)?;
+                    if angle % 90 != 0 {
+                        return Err(invalid());
+                    }
+                    spec.rotate = Some(angle);
+                }
+                "fit" => {
+                    spec.fit = match value.to_lowercase().as_str() {
+                        "inside" => Fit::Inside,
+                        "outside" => Fit::Outside,
+                        "cover" => Fit::Cover,
+                        "contain" => Fit::Contain,
+                        "fill" => Fit::Fill,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "gravity" => spec.gravity = parse_gravity(&value).ok_or_else(invalid)?,
+                "format" => {
+                    let format = ImageFormat::from_name(&value).ok_or_else(invalid)?;
+                    if !format.can_save() {
+                        return Err(invalid());
+                    }
+                    spec.format = Some(format);
+                }
+                _ => {
+                    spec.strip = match value.as_str() {
+                        "" | "1" | "true" => true,
+                        "0" | "false" => false,
+                        _ => return Err(invalid()),
+                    }
+                }
+            }
+        }
+        if spec.fit != Fit::Inside && (spec.width.is_none() || spec.height.is_none()) {
+            return Err(SpecError::MissingDimension(spec.fit));
+        }
+        Ok(spec)
+    }
+
+    /// runs the transformation on an encoded image
+    pub fn apply(&self, buf: &[u8]) -> Result<Vec<u8>> {
+        self.apply_with_opts(buf, &ActionOptions::default())
+    }
+
+    pub fn apply_with_opts(&self, buf: &[u8], options: &ActionOptions) -> Result<Vec<u8>> {
+        let input_type = action::vips_image_type(buf);
+        let _type = match self.format {
+            Some(format) if !format.can_save() => return Err(Error::UnsupportedFormat(format.name())),
+            Some(format) => format.name().to_string(),
+            None => input_type.clone(),
+        };
+        let mut image = match (self.width, self.height) {
+            (None, None) => action::load(buf, options)?,
+            // a missing side is left unconstrained, which only happens with `Fit::Inside`
+            (width, height) => {
+                let resize_options = ResizeOptions {
+                    fit: self.fit,
+                    gravity: self.gravity,
+                    ..ResizeOptions::default()
+                };
+                let width = width.unwrap_or(MAX_DIMENSION);
+                let height = height.unwrap_or(MAX_DIMENSION);
+                action::thumbnail_fit(buf, &input_type, width, height, &resize_options, options)?
+            }
+        };
+        if let Some(angle) = self.rotate {
+            image = action::rotate_image(&image, f64::from(angle), &RotateOptions::default())?;
+        }
+        if self.strip {
+            image = action::strip_image(&image, StripPolicy::KeepIcc)?;
+        }
+        let encode_options = EncodeOptions {
+            quality: self.quality.or(options.encode.quality),
+            ..options.encode.clone()
+        };
+        action::save(&image, &_type, &encode_options)
+    }
+}
+
+fn parse_int(key: &str, value: &str, min: i32, max: i32) -> std::result::Result<i32, SpecError> {
+    let number: i64 = value.parse().map_err(|_| SpecError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+    })?;
+    if number < i64::from(min) || number > i64::from(max) {
+        return Err(SpecError::OutOfRange {
+            key: key.to_string(),
+            value: number,
+            min: i64::from(min),
+            max: i64::from(max),
+        });
+    }
+    Ok(number as i32)
+}
+
+fn parse_gravity(value: &str) -> Option<Gravity> {
+    let gravity = match value.to_lowercase().as_str() {
+        "centre" | "center" | "ce" => Gravity::Centre,
+        "north" | "no" | "n" => Gravity::North,
+        "east" | "ea" | "e" => Gravity::East,
+        "south" | "so" | "s" => Gravity::South,
+        "west" | "we" | "w" => Gravity::West,
+        "northeast" | "noea" | "ne" => Gravity::NorthEast,
+        "southeast" | "soea" | "se" => Gravity::SouthEast,
+        "southwest" | "sowe" | "sw" => Gravity::SouthWest,
+        "northwest" | "nowe" | "nw" => Gravity::NorthWest,
+        "entropy" | "smart" => Gravity::Entropy,
+        "attention" => Gravity::Attention,
+        _ => return None,
+    };
+    Some(gravity)
+}
+
+// decodes `%XX` escapes and `+` as a space; malformed escapes are kept as they are
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let digits = &bytes[i + 1..i + 3];
+                // from_str_radix alone would also take a sign, as in `%+1`
+                let hex = std::str::from_utf8(digits).ok().filter(|_| digits.iter().all(u8::is_ascii_hexdigit));
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -98,3 +98,17 @@ pub unsafe fn new_int_array(array: *mut i32, size: u64) -> Vec<i32> {
 pub unsafe fn new_double_array(array: *mut f64, size: u64) -> Vec<f64> {
     Vec::from(std::slice::from_raw_parts(array as *mut f64, size as usize))
 }
+
+/// parses `#RRGGBB` or `#RGB` into 0-255 band values
+pub(crate) fn parse_hex_colour(color: &str) -> Result<Vec<f64>> {
+    let hex = color.trim().trim_start_matches('#');
+    let digits: Vec<u32> = hex.chars().map(|c| c.to_digit(16)).collect::<Option<_>>().ok_or(
+        Error::OperationError("Colour must be a #RRGGBB or #RGB hex value"),
+    )?;
+    let colour = match digits.len() {
+        3 => digits.iter().map(|d| f64::from(d * 17)).collect(),
+        6 => digits.chunks(2).map(|d| f64::from(d[0] * 16 + d[1])).collect(),
+        _ => return Err(Error::OperationError("Colour must be a #RRGGBB or #RGB hex value")),
+    };
+    Ok(colour)
+}
@@ -0,0 +1,136 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+fn frames(buf: &[u8]) -> (i32, i32, i32) {
+    let image = VipsImage::new_from_buffer(buf, "n=-1").unwrap();
+    (image.get_width(), image.get_page_height(), image.get_n_pages())
+}
+
+#[test]
+fn test_animation_frames() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.gif").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let (width, page_height, n_pages) = frames(&buffer);
+
+    let result = action::crop(&buffer, 10, 10, action::Gravity::Centre).unwrap();
+    assert_eq!(frames(&result), (10, 10, n_pages));
+
+    let result = action::rotate(&buffer, 90).unwrap();
+    assert_eq!(frames(&result), (page_height, width, n_pages));
+
+    let result = action::watermark_text(&buffer, "libvips", 72, 0.5, "#ffffff").unwrap();
+    assert_eq!(frames(&result), (width, page_height, n_pages));
+
+    let result = action::resize(&buffer, 20, 20).unwrap();
+    let (_, _, resized_pages) = frames(&result);
+    assert_eq!(resized_pages, n_pages);
+
+    let result = action::resize_scale(&buffer, 0.5, ops::Kernel::Lanczos3).unwrap();
+    let (_, scaled_page_height, scaled_pages) = frames(&result);
+    assert_eq!(scaled_pages, n_pages);
+    assert_eq!(scaled_page_height, (f64::from(page_height) * 0.5).round() as i32);
+
+    // formats without frames keep the first one
+    let result = action::format(&buffer, action::ImageFormat::Png).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (width, page_height));
+}
+
+fn timing(buf: &[u8]) -> (Vec<i32>, i32) {
+    let image = VipsImage::new_from_buffer(buf, "n=-1").unwrap();
+    (image.get_array_int("delay").unwrap(), image.get_int("loop").unwrap())
+}
+
+#[test]
+fn test_animation_timing() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.gif").unwrap();
+    let mut gif: Vec<u8> = Vec::new();
+    f.read_to_end(&mut gif).unwrap();
+    let webp = action::format(&gif, action::ImageFormat::Webp).unwrap();
+
+    for input in [gif, webp].iter() {
+        let expected = timing(input);
+        let n_pages = expected.0.len() as i32;
+        let results = vec![
+            action::crop(input, 10, 10, action::Gravity::Entropy).unwrap(),
+            action::rotate(input, 90).unwrap(),
+            action::resize(input, 20, 20).unwrap(),
+            action::watermark_text(input, "libvips", 72, 0.5, "#ffffff").unwrap(),
+        ];
+        for result in results.iter() {
+            assert_eq!(action::detect_format(result), action::detect_format(input));
+            assert_eq!(frames(result).2, n_pages);
+            assert_eq!(timing(result), expected);
+        }
+    }
+}
+
+// two 200x50 frames whose blue band is the x coordinate, each with a white square in a different place
+fn moving_square_webp() -> Vec<u8> {
+    let x = ops::extract_band(&ops::xyz(200, 50).unwrap(), 0).unwrap();
+    let blue = ops::cast(&x, ops::BandFormat::Uchar).unwrap();
+    let square = ops::invert(&ops::black(40, 40).unwrap()).unwrap();
+    let mut frames: Vec<VipsImage> = [10, 150]
+        .iter()
+        .map(|left| {
+            let black = ops::black(200, 50).unwrap();
+            let marked = ops::insert(&black, &square, *left, 5).unwrap();
+            let image = ops::bandjoin(&mut [marked.clone(), marked, blue.clone()]).unwrap();
+            ops::copy_with_interpretation(&image, ops::Interpretation::Srgb).unwrap()
+        })
+        .collect();
+    let arrayjoin_options = ops::ArrayjoinOptions {
+        across: 1,
+        background: vec![0.0],
+        hspacing: 200,
+        vspacing: 50,
+        ..ops::ArrayjoinOptions::default()
+    };
+    let joined = ops::arrayjoin_with_opts(&mut frames, &arrayjoin_options).unwrap();
+    let mut animation = ops::copy(&joined).unwrap();
+    animation.set_int("page-height", 50).unwrap();
+    animation.image_write_to_buffer(".webp[lossless]").unwrap()
+}
+
+// the x coordinate each frame of the result starts at, read back from the blue band
+fn crop_offsets(buf: &[u8]) -> Vec<f64> {
+    let image = VipsImage::new_from_buffer(buf, "n=-1").unwrap();
+    let page_height = image.get_page_height();
+    (0..image.get_height() / page_height)
+        .map(|page| ops::getpoint(&image, 0, page * page_height + page_height / 2).unwrap()[2])
+        .collect()
+}
+
+#[test]
+fn test_animation_cover_attention() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let buffer = moving_square_webp();
+    let resize_options = action::ResizeOptions {
+        fit: action::Fit::Cover,
+        gravity: action::Gravity::Attention,
+        ..action::ResizeOptions::default()
+    };
+    let options = action::ActionOptions {
+        encode: action::EncodeOptions {
+            lossless: true,
+            ..action::EncodeOptions::default()
+        },
+        ..action::ActionOptions::default()
+    };
+
+    let results = vec![
+        action::resize_with_opts(&buffer, 50, 50, &resize_options, &options).unwrap(),
+        action::Pipeline::with_opts(&options).resize_with_opts(50, 50, &resize_options).run(&buffer).unwrap(),
+    ];
+    for result in results.iter() {
+        assert_eq!(frames(result), (50, 50, 2));
+        let offsets = crop_offsets(result);
+        // the window follows the square in the first frame and stays there
+        assert!(offsets[0] < 50.0);
+        assert!(offsets.iter().all(|offset| (offset - offsets[0]).abs() < 1.0));
+    }
+}
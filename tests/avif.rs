@@ -0,0 +1,57 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+#[test]
+fn test_avif_round_trip() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    if !action::ImageFormat::Avif.can_save() {
+        println!("libvips was built without HEIF support, skipping");
+        return
+    }
+    let mut f = File::open("images/test.png").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let input = VipsImage::new_from_buffer(&buffer, "").unwrap();
+
+    let result = action::format(&buffer, action::ImageFormat::Avif).unwrap();
+    assert_eq!(action::detect_format(&result), Some(action::ImageFormat::Avif));
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (input.get_width(), input.get_height()));
+
+    let encode = |encode_options: action::EncodeOptions| {
+        let options = action::ActionOptions {
+            encode: encode_options,
+            ..action::ActionOptions::default()
+        };
+        action::format_with_opts(&buffer, action::ImageFormat::Avif, &options).unwrap()
+    };
+    let low = encode(action::EncodeOptions {
+        quality: Some(20),
+        heif_effort: Some(2),
+        subsample: ops::ForeignJpegSubsample::On,
+        ..action::EncodeOptions::default()
+    });
+    let high = encode(action::EncodeOptions {
+        quality: Some(90),
+        ..action::EncodeOptions::default()
+    });
+    assert!(low.len() < high.len());
+    assert_eq!(action::detect_format(&low), Some(action::ImageFormat::Avif));
+
+    let lossless = encode(action::EncodeOptions {
+        lossless: true,
+        ..action::EncodeOptions::default()
+    });
+    assert_eq!(action::detect_format(&lossless), Some(action::ImageFormat::Avif));
+
+    let invalid = action::EncodeOptions {
+        bitdepth: 9,
+        ..action::EncodeOptions::default()
+    };
+    let options = action::ActionOptions {
+        encode: invalid,
+        ..action::ActionOptions::default()
+    };
+    assert!(action::format_with_opts(&buffer, action::ImageFormat::Avif, &options).is_err());
+}
@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::io::prelude::*;
-use libvips::{action, VipsApp};
+use libvips::{action, VipsApp, VipsImage};
+use libvips::action::Gravity;
 
 #[test]
 fn test_crop() {
@@ -12,7 +13,7 @@ fn test_crop() {
         f.read_to_end(&mut buffer);
 
         let mut file = File::create(format!("images/crop_{}", i)).unwrap();
-        let result = match action::crop(&buffer, 100, 100, 0) {
+        let result = match action::crop(&buffer, 100, 100, Gravity::Centre) {
             Ok(_result) => _result,
             Err(e) => {
                 println!("failed to crop {}, err: {:?}", i, e);
@@ -22,3 +23,35 @@ fn test_crop() {
         file.write(&result);
     }
 }
+
+#[test]
+fn test_crop_gravity() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+
+    let gravities = vec![
+        Gravity::Centre,
+        Gravity::North,
+        Gravity::East,
+        Gravity::South,
+        Gravity::West,
+        Gravity::NorthEast,
+        Gravity::SouthEast,
+        Gravity::SouthWest,
+        Gravity::NorthWest,
+        Gravity::Entropy,
+        Gravity::Attention,
+    ];
+    for gravity in gravities.into_iter() {
+        let result = action::crop(&buffer, 300, 200, gravity).unwrap();
+        let image = VipsImage::new_from_buffer(&result, "").unwrap();
+        assert_eq!((image.get_width(), image.get_height()), (300, 200));
+    }
+
+    // larger than the image: clamped to the image size
+    let result = action::crop(&buffer, 5000, 100, Gravity::West).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (1200, 100));
+}
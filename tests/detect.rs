@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::action::{self, ImageFormat};
+use libvips::VipsApp;
+
+fn read(name: &str) -> Vec<u8> {
+    let mut f = File::open(format!("images/{}", name)).unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+    buffer
+}
+
+#[test]
+fn test_detect_format_files() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    assert_eq!(action::detect_format(&read("test.jpeg")), Some(ImageFormat::Jpeg));
+    assert_eq!(action::detect_format(&read("test.png")), Some(ImageFormat::Png));
+    assert_eq!(action::detect_format(&read("test.webp")), Some(ImageFormat::Webp));
+    assert_eq!(action::detect_format(&read("test.tiff")), Some(ImageFormat::Tiff));
+    assert_eq!(action::detect_format(&read("test.gif")), Some(ImageFormat::Gif));
+    // despite its name, test.bmp holds JPEG data
+    assert_eq!(action::detect_format(&read("test.bmp")), Some(ImageFormat::Jpeg));
+    // a 64 x 48, 24-bit BMP; it only loads through ImageMagick
+    let bmp = read("test_bgr24.bmp");
+    assert_eq!(action::detect_format(&bmp), Some(ImageFormat::Bmp));
+    if ImageFormat::Bmp.can_load() {
+        let metadata = action::get_metadata(&bmp).unwrap();
+        assert_eq!(metadata.ttype, "bmp");
+        assert_eq!((metadata.width, metadata.height), (64, 48));
+    }
+    assert_eq!(action::detect_format(b"definitely not an image"), None);
+}
+
+#[test]
+fn test_detect_format_signatures() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut bmp = b"BM\x00\x00\x00\x00\x00\x00\x00\x00\x36\x00\x00\x00".to_vec();
+    bmp.extend_from_slice(&[40, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(action::detect_format(&bmp), Some(ImageFormat::Bmp));
+
+    let ico = [0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x10, 0x10, 0x00, 0x00, 0x01, 0x00];
+    assert_eq!(action::detect_format(&ico), Some(ImageFormat::Ico));
+
+    let psd = b"8BPS\x00\x01\x00\x00\x00\x00\x00\x00\x00\x03";
+    assert_eq!(action::detect_format(psd), Some(ImageFormat::Psd));
+
+    let jxl_codestream = [0xFF, 0x0A, 0xFA, 0x7F, 0x01, 0x90, 0x08, 0x06, 0x01, 0x00, 0x48, 0x00];
+    assert_eq!(action::detect_format(&jxl_codestream), Some(ImageFormat::Jxl));
+    let jxl_container = [0x00, 0x00, 0x00, 0x0C, b'J', b'X', b'L', b' ', 0x0D, 0x0A, 0x87, 0x0A];
+    assert_eq!(action::detect_format(&jxl_container), Some(ImageFormat::Jxl));
+
+    let avif = b"\x00\x00\x00\x1cftypavif\x00\x00\x00\x00avifmif1miaf";
+    assert_eq!(action::detect_format(avif), Some(ImageFormat::Avif));
+    let avif_mif1 = b"\x00\x00\x00\x18ftypmif1\x00\x00\x00\x00mif1avif";
+    assert_eq!(action::detect_format(avif_mif1), Some(ImageFormat::Avif));
+    let heic = b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00mif1heic";
+    assert_eq!(action::detect_format(heic), Some(ImageFormat::Heif));
+
+    let svg = b"\xef\xbb\xbf<?xml version=\"1.0\"?>\n<!-- logo -->\n<!DOCTYPE svg>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+    assert_eq!(action::detect_format(svg), Some(ImageFormat::Svg));
+}
@@ -0,0 +1,72 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp};
+
+fn options(encode: action::EncodeOptions) -> action::ActionOptions {
+    action::ActionOptions {
+        encode,
+        ..action::ActionOptions::default()
+    }
+}
+
+#[test]
+fn test_encode_options() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let low = action::EncodeOptions {
+        quality: Some(10),
+        ..action::EncodeOptions::default()
+    };
+    let high = action::EncodeOptions {
+        quality: Some(95),
+        progressive: true,
+        optimize_coding: true,
+        subsample: ops::ForeignJpegSubsample::Off,
+        ..action::EncodeOptions::default()
+    };
+    let small = action::resize_with_opts(&buffer, 600, 400, &action::ResizeOptions::default(), &options(low)).unwrap();
+    let large = action::resize_with_opts(&buffer, 600, 400, &action::ResizeOptions::default(), &options(high)).unwrap();
+    assert!(small.len() < large.len());
+
+    let webp = action::EncodeOptions {
+        lossless: true,
+        effort: 6,
+        ..action::EncodeOptions::default()
+    };
+    let result = action::format_with_opts(&buffer, action::ImageFormat::Webp, &options(webp)).unwrap();
+    assert_eq!(action::get_metadata(&result).unwrap().ttype, "webp");
+
+    let png = action::EncodeOptions {
+        compression: 9,
+        palette: true,
+        quality: Some(80),
+        ..action::EncodeOptions::default()
+    };
+    let result = action::format_with_opts(&buffer, action::ImageFormat::Png, &options(png)).unwrap();
+    assert_eq!(action::get_metadata(&result).unwrap().ttype, "png");
+
+    let tiff = action::EncodeOptions {
+        tiff_compression: ops::ForeignTiffCompression::Lzw,
+        ..action::EncodeOptions::default()
+    };
+    let result = action::format_with_opts(&buffer, action::ImageFormat::Tiff, &options(tiff)).unwrap();
+    assert_eq!(action::get_metadata(&result).unwrap().ttype, "tiff");
+
+    let invalid = action::EncodeOptions {
+        quality: Some(0),
+        ..action::EncodeOptions::default()
+    };
+    assert!(action::format_with_opts(&buffer, action::ImageFormat::Jpeg, &options(invalid)).is_err());
+
+    // bitdepth only applies to gif, heif and avif
+    let deep = action::EncodeOptions {
+        bitdepth: 10,
+        ..action::EncodeOptions::default()
+    };
+    let result = action::format_with_opts(&buffer, action::ImageFormat::Jpeg, &options(deep.clone())).unwrap();
+    assert_eq!(action::get_metadata(&result).unwrap().ttype, "jpeg");
+    assert!(action::format_with_opts(&buffer, action::ImageFormat::Gif, &options(deep)).is_err());
+}
@@ -0,0 +1,75 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::exif::{self, Orientation, Rational};
+use libvips::{action, ops, VipsApp, VipsImage};
+
+#[test]
+fn test_exif_value() {
+    assert_eq!(
+        exif::exif_value("52/1 31/1 1234/100 (52, 31, 12.34, Rational, 3 components, 24 bytes)"),
+        "52/1 31/1 1234/100"
+    );
+    assert_eq!(
+        exif::exif_value("6 (Right-top, Short, 1 components, 2 bytes)"),
+        "6"
+    );
+    assert_eq!(
+        exif::exif_value("Foo (Bar) (Foo (Bar), ASCII, 10 components, 10 bytes)"),
+        "Foo (Bar)"
+    );
+}
+
+#[test]
+fn test_exif_parsers() {
+    assert_eq!(
+        exif::parse_rational("28/10"),
+        Some(Rational {
+            numerator: 28,
+            denominator: 10
+        })
+    );
+    assert_eq!(exif::parse_rational("-1/3").unwrap().to_f64(), Some(-1.0 / 3.0));
+    assert_eq!(exif::parse_rational("1/0").unwrap().to_f64(), None);
+    assert!(exif::parse_rational("abc").is_none());
+
+    let latitude = exif::parse_gps_coordinate("52/1 30/1 36/1", "N").unwrap();
+    assert!((latitude - 52.51).abs() < 1e-9);
+    let longitude = exif::parse_gps_coordinate("13/1 24/1 0/1", "W").unwrap();
+    assert!((longitude + 13.4).abs() < 1e-9);
+
+    assert_eq!(exif::parse_datetime("1970:01:01 00:00:00"), Some(0));
+    assert_eq!(exif::parse_datetime("2020:02:29 12:30:15"), Some(1582979415));
+    assert_eq!(exif::parse_datetime("0000:00:00 00:00:00"), None);
+    assert_eq!(exif::parse_datetime("2023:02:31 10:00:00"), None);
+    assert_eq!(exif::parse_datetime("2023:02:29 10:00:00"), None);
+    assert_eq!(exif::parse_datetime("2023:04:31 10:00:00"), None);
+    assert_eq!(exif::parse_datetime("1900:02:29 10:00:00"), None);
+    assert_eq!(exif::parse_datetime("2000:02:29 00:00:00"), Some(951782400));
+
+    assert_eq!(Orientation::from_i32(6), Some(Orientation::RightTop));
+    assert_eq!(Orientation::from_i32(9), None);
+}
+
+#[test]
+fn test_get_exif() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let image = VipsImage::new_from_file("images/test.jpeg").unwrap();
+    let mut image = ops::copy(&image).unwrap();
+    image.set_string("exif-ifd0-Orientation", "6").unwrap();
+    image.set_string("exif-ifd3-GPSLatitudeRef", "S").unwrap();
+    image.set_string("exif-ifd3-GPSLatitude", "33/1 51/1 36/1").unwrap();
+    image.set_string("exif-ifd3-GPSLongitudeRef", "E").unwrap();
+    image.set_string("exif-ifd3-GPSLongitude", "151/1 12/1 36/1").unwrap();
+    let buffer = image.image_write_to_buffer(".jpeg").unwrap();
+
+    let exif = action::get_exif(&buffer).unwrap();
+    println!("{:?}", exif);
+    assert_eq!(exif.orientation, Some(Orientation::RightTop));
+    assert!((exif.gps_latitude.unwrap() + 33.86).abs() < 1e-6);
+    assert!((exif.gps_longitude.unwrap() - 151.21).abs() < 1e-6);
+
+    let mut f = File::open("images/test.png").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    assert_eq!(action::get_exif(&buffer).unwrap().gps_latitude, None);
+}
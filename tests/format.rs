@@ -1,5 +1,7 @@
 use std::fs::File;
 use std::io::prelude::*;
+use libvips::action::ImageFormat;
+use libvips::error::Error;
 use libvips::{action, VipsApp};
 
 #[test]
@@ -7,19 +9,19 @@ fn test_format() {
     let app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
 
     let images = vec!["test.png", "test.webp", "test.tiff"];
-    format_type(images, "jpeg");
+    format_type(images, ImageFormat::Jpeg);
 
     let images = vec!["test.jpeg", "test.webp", "test.tiff"];
-    format_type(images, "png");
+    format_type(images, ImageFormat::Png);
 
     let images = vec!["test.jpeg", "test.png", "test.tiff"];
-    format_type(images, "webp");
+    format_type(images, ImageFormat::Webp);
 
     let images = vec!["test.jpeg", "test.png", "test.webp"];
-    format_type(images, "tiff");
+    format_type(images, ImageFormat::Tiff);
 }
 
-fn format_type(images: Vec<&str>, ftype: &str) {
+fn format_type(images: Vec<&str>, ftype: ImageFormat) {
     for i in images.iter() {
         let mut f = File::open(format!("images/{}", i)).unwrap();
         let mut buffer: Vec<u8> = Vec::new();
@@ -28,7 +30,7 @@ fn format_type(images: Vec<&str>, ftype: &str) {
         let strs: Vec<&str> = i.split(".").collect();
         let mut file = File::create(format!(
             "images/format_{}_from_{}.{}",
-            ftype, strs[1], ftype
+            ftype.name(), strs[1], ftype.name()
         ))
         .unwrap();
 
@@ -42,3 +44,26 @@ fn format_type(images: Vec<&str>, ftype: &str) {
         file.write(&result);
     }
 }
+
+#[test]
+fn test_supported_formats() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let formats = action::supported_formats();
+    assert_eq!(formats.len(), ImageFormat::ALL.len());
+    let jpeg = formats.iter().find(|f| f.format == ImageFormat::Jpeg).unwrap();
+    assert!(jpeg.load && jpeg.save);
+    let pdf = formats.iter().find(|f| f.format == ImageFormat::Pdf).unwrap();
+    assert!(!pdf.save);
+
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+    match action::format(&buffer, ImageFormat::Pdf) {
+        Err(Error::UnsupportedFormat(name)) => assert_eq!(name, "pdf"),
+        other => panic!("expected UnsupportedFormat, got {:?}", other.map(|b| b.len())),
+    }
+
+    assert_eq!(ImageFormat::from_name("JPG"), Some(ImageFormat::Jpeg));
+    assert_eq!(ImageFormat::from_name(".heic"), Some(ImageFormat::Heif));
+    assert_eq!(ImageFormat::from_name("exe"), None);
+}
@@ -0,0 +1,33 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+#[test]
+fn test_gifsave() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.gif").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let input = VipsImage::new_from_buffer(&buffer, "n=-1").unwrap();
+
+    let options = ops::GifsaveBufferOptions {
+        dither: 0.0,
+        effort: 1,
+        bitdepth: 4,
+        ..ops::GifsaveBufferOptions::default()
+    };
+    let result = match ops::gifsave_buffer_with_opts(&input, &options) {
+        Ok(result) => result,
+        Err(e) => {
+            println!("libvips can't save gif itself, skipping: {:?}", e);
+            return
+        }
+    };
+    assert_eq!(action::detect_format(&result), Some(action::ImageFormat::Gif));
+    let image = VipsImage::new_from_buffer(&result, "n=-1").unwrap();
+    assert_eq!(image.get_n_pages(), input.get_n_pages());
+    assert_eq!(image.get_page_height(), input.get_page_height());
+
+    let result = action::resize(&buffer, 20, 20).unwrap();
+    assert_eq!(action::detect_format(&result), Some(action::ImageFormat::Gif));
+}
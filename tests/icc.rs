@@ -0,0 +1,29 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, bindings, ops, VipsApp};
+
+#[test]
+fn test_icc() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let images = vec!["test.jpeg", "test.png", "test.webp", "test.tiff"];
+    if unsafe { bindings::vips_icc_present() } == 0 {
+        println!("libvips was built without lcms, skipping");
+        return
+    }
+    let srgb = ops::profile_load("srgb").unwrap();
+    assert!(!srgb.is_empty());
+    for i in images.iter() {
+        let mut f = File::open(format!("images/{}", i)).unwrap();
+        let mut buffer: Vec<u8> = Vec::new();
+        f.read_to_end(&mut buffer).unwrap();
+
+        let embedded = action::embed_icc_profile(&buffer, &srgb).unwrap();
+        assert_eq!(action::get_icc_profile(&embedded).unwrap(), Some(srgb.clone()));
+
+        // the embedded copy is known to carry a profile, so converting it must work
+        let result = action::icc_to_srgb(&embedded, ops::Intent::Perceptual).unwrap();
+        assert!(action::get_icc_profile(&result).unwrap().is_some());
+        let mut file = File::create(format!("images/icc_srgb_{}", i)).unwrap();
+        file.write_all(&result).unwrap();
+    }
+}
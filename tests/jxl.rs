@@ -0,0 +1,53 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+#[test]
+fn test_jxl_lossless_round_trip() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    if !action::ImageFormat::Jxl.can_save() {
+        println!("libvips was built without JPEG XL support, skipping");
+        return
+    }
+    let mut f = File::open("images/test.png").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let input = VipsImage::new_from_buffer(&buffer, "").unwrap();
+
+    let options = ops::JxlsaveBufferOptions {
+        lossless: true,
+        effort: 3,
+        ..ops::JxlsaveBufferOptions::default()
+    };
+    let result = ops::jxlsave_buffer_with_opts(&input, &options).unwrap();
+    assert_eq!(action::detect_format(&result), Some(action::ImageFormat::Jxl));
+
+    let image = ops::jxlload_buffer(&result).unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (input.get_width(), input.get_height()));
+    let difference = ops::subtract(&image, &input).unwrap();
+    let difference = ops::abs(&difference).unwrap();
+    assert_eq!(ops::max(&difference).unwrap(), 0.0);
+
+    let options = ops::JxlsaveBufferOptions {
+        distance: 4.0,
+        tier: 2,
+        ..ops::JxlsaveBufferOptions::default()
+    };
+    let lossy = ops::jxlsave_buffer_with_opts(&input, &options).unwrap();
+    assert!(lossy.len() < result.len());
+
+    let options = ops::JxlsaveBufferOptions {
+        q: 20,
+        ..ops::JxlsaveBufferOptions::default()
+    };
+    let low_quality = ops::jxlsave_buffer_with_opts(&input, &options).unwrap();
+    let options = ops::JxlsaveBufferOptions {
+        q: 95,
+        ..ops::JxlsaveBufferOptions::default()
+    };
+    let high_quality = ops::jxlsave_buffer_with_opts(&input, &options).unwrap();
+    assert!(low_quality.len() < high_quality.len());
+
+    let result = action::format(&buffer, action::ImageFormat::Jxl).unwrap();
+    assert_eq!(action::detect_format(&result), Some(action::ImageFormat::Jxl));
+}
@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::prelude::*;
-use libvips::{action, VipsApp};
+use libvips::{action, ops, HeaderType, HeaderValue, VipsApp, VipsImage};
 
 #[test]
 fn test_get_metadata() {
@@ -16,6 +16,90 @@ fn test_get_metadata() {
         let mut f = File::open(format!("images/{}", i)).unwrap();
         let mut buffer: Vec<u8> = Vec::new();
         f.read_to_end(&mut buffer);
-        println!("{:?}", action::get_metadata(&buffer));
+        let metadata = action::get_metadata(&buffer).unwrap();
+        println!("{:?}", metadata);
+        assert!(metadata.width > 0 && metadata.height > 0);
+        assert!(!metadata.space.is_empty());
     }
 }
+
+#[test]
+fn test_get_metadata_exif() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test_orientation_6.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let metadata = action::get_metadata(&buffer).unwrap();
+    assert_eq!(metadata.ttype, "jpeg");
+    // the fixture carries a single EXIF tag, Orientation = 6
+    assert!(metadata.orientation.starts_with('6'), "{}", metadata.orientation);
+    assert_eq!(metadata.make, "");
+    assert_eq!(metadata.gps_latitude, "");
+
+    let image = VipsImage::new_from_buffer(&buffer, "").unwrap();
+    assert_eq!(image.get_int("orientation").unwrap(), 6);
+    assert_eq!(ops::image_get_string(&image, "exif-ifd0-Orientation"), metadata.orientation);
+    assert_eq!(ops::image_get_string(&image, "exif-ifd0-NotAField"), "");
+}
+
+#[test]
+fn test_get_metadata_fields() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let image = VipsImage::new_from_file("images/test.jpeg").unwrap();
+    assert!(image.get_int("width").is_ok());
+    assert!(image.get_double("xres").is_ok());
+    assert!(image.get_string("exif-ifd0-NotAField").is_err());
+    assert!(ops::image_get_blob(&image, "not-a-blob").is_err());
+}
+
+#[test]
+fn test_get_header() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let image = VipsImage::new_from_file("images/test.jpeg").unwrap();
+    let fields = image.get_fields();
+    assert!(fields.iter().any(|f| f == "width"));
+    assert_eq!(image.get_typeof("width").unwrap(), Some(HeaderType::Int));
+    assert_eq!(image.get_typeof("xres").unwrap(), Some(HeaderType::Double));
+    assert_eq!(image.get_typeof("not-a-field").unwrap(), None);
+
+    let header = image.get_header().unwrap();
+    assert_eq!(header.len(), fields.len());
+    for (name, value) in header.iter() {
+        println!("{}: {:?}", name, value);
+    }
+}
+
+#[test]
+fn test_set_and_remove_fields() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let image = VipsImage::new_from_file("images/test.jpeg").unwrap();
+    let mut image = ops::copy(&image).unwrap();
+
+    image.set_string("exif-ifd0-Copyright", "(c) libvips").unwrap();
+    image.set_int("orientation", 6).unwrap();
+    image.set_blob("custom-blob", &[1, 2, 3]).unwrap();
+    image.set_array_double("custom-array", &[1.0, 2.5]).unwrap();
+    assert_eq!(image.get_string("exif-ifd0-Copyright").unwrap(), "(c) libvips");
+    assert_eq!(image.get_int("orientation").unwrap(), 6);
+    assert_eq!(image.get_blob("custom-blob").unwrap(), vec![1, 2, 3]);
+    assert_eq!(image.get_array_double("custom-array").unwrap(), vec![1.0, 2.5]);
+
+    // a cloned image value holds its own reference, so both copies can be dropped
+    let thumbnail = ops::black(8, 8).unwrap();
+    image.set_image("custom-image", &thumbnail).unwrap();
+    let value = image.get_value("custom-image").unwrap();
+    let copy = value.clone();
+    drop(value);
+    match copy {
+        HeaderValue::Image(copy) => assert_eq!(copy.get_width(), 8),
+        other => panic!("unexpected value {:?}", other),
+    }
+
+    assert!(image.remove_field("custom-blob").unwrap());
+    assert!(!image.remove_field("custom-blob").unwrap());
+    assert!(image.get_blob("custom-blob").is_err());
+
+    let buffer = image.image_write_to_buffer(".jpeg").unwrap();
+    let reloaded = VipsImage::new_from_buffer(&buffer, "").unwrap();
+    assert_eq!(reloaded.get_int("orientation").unwrap(), 6);
+}
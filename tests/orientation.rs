@@ -0,0 +1,52 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, VipsApp, VipsImage};
+
+// images/test_orientation_6.jpeg is test.jpeg (1200x800) tagged with EXIF orientation 6 (rotate 90° to display)
+fn rotated_fixture() -> Vec<u8> {
+    let mut f = File::open("images/test_orientation_6.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    buffer
+}
+
+fn assert_upright(buffer: &[u8], width: i32, height: i32) {
+    let image = VipsImage::new_from_buffer(buffer, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (width, height));
+    assert_eq!(image.get_int("orientation").unwrap_or(1), 1);
+}
+
+#[test]
+fn test_auto_orient() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let buffer = rotated_fixture();
+
+    assert_upright(&action::rotate(&buffer, 0).unwrap(), 800, 1200);
+    assert_upright(&action::rotate(&buffer, 90).unwrap(), 1200, 800);
+    assert_upright(&action::resize(&buffer, 100, 100).unwrap(), 67, 100);
+    assert_upright(&action::crop(&buffer, 800, 100, action::Gravity::Centre).unwrap(), 800, 100);
+    assert_upright(
+        &action::watermark_text(&buffer, "watermark", 300, 0.5, "#FFFFFF").unwrap(),
+        800,
+        1200,
+    );
+}
+
+#[test]
+fn test_auto_orient_opt_out() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let buffer = rotated_fixture();
+    let options = action::ActionOptions {
+        auto_orient: false,
+        ..action::ActionOptions::default()
+    };
+
+    let result = action::rotate_with_opts(&buffer, 0.0, &action::RotateOptions::default(), &options).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (1200, 800));
+    assert_eq!(image.get_int("orientation").unwrap(), 6);
+
+    let result = action::resize_with_opts(&buffer, 100, 100, &action::ResizeOptions::default(), &options).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (100, 67));
+}
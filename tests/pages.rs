@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, VipsApp, VipsImage};
+
+#[test]
+fn test_pages() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.tiff").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let input = VipsImage::new_from_buffer(&buffer, "").unwrap();
+
+    let n_pages = action::page_count(&buffer).unwrap();
+    assert_eq!(n_pages, input.get_n_pages());
+
+    let result = action::page(&buffer, 0, action::ImageFormat::Png).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!(image.get_width(), input.get_width());
+
+    assert!(action::page(&buffer, n_pages, action::ImageFormat::Png).is_err());
+    assert!(action::page(&buffer, -1, action::ImageFormat::Png).is_err());
+}
+
+#[test]
+fn test_contact_sheet() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.gif").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let n_pages = action::page_count(&buffer).unwrap();
+
+    let options = action::ContactSheetOptions {
+        across: 2,
+        tile_size: 64,
+        spacing: 4,
+        ..action::ContactSheetOptions::default()
+    };
+    let result =
+        action::contact_sheet_with_opts(&buffer, action::ImageFormat::Png, &options, &action::ActionOptions::default())
+            .unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    let across = std::cmp::min(2, n_pages);
+    let down = (n_pages + 1) / 2;
+    assert_eq!((image.get_width(), image.get_height()), (across * 68, down * 68));
+    assert_eq!(image.get_bands(), 3);
+}
+
+#[test]
+fn test_page_orientation() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    // 1200x800, tagged with EXIF orientation 6
+    let mut f = File::open("images/test_orientation_6.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let size = |buf: &[u8]| {
+        let image = VipsImage::new_from_buffer(buf, "").unwrap();
+        (image.get_width(), image.get_height())
+    };
+
+    let result = action::page(&buffer, 0, action::ImageFormat::Png).unwrap();
+    assert_eq!(size(&result), (1200, 800));
+
+    let result =
+        action::page_with_opts(&buffer, 0, action::ImageFormat::Png, &action::ActionOptions::default()).unwrap();
+    assert_eq!(size(&result), (800, 1200));
+}
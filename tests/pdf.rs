@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+// images/test.pdf has two pages: 72x72 and 144x72 points
+#[test]
+fn test_pdfload() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.pdf").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    if !action::ImageFormat::Pdf.can_load() {
+        println!("libvips was built without PDF support, skipping");
+        return
+    }
+    assert_eq!(action::detect_format(&buffer), Some(action::ImageFormat::Pdf));
+    assert_eq!(action::page_count(&buffer).unwrap(), 2);
+
+    let image = ops::pdfload_buffer(&buffer).unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (72, 72));
+
+    let options = ops::PdfloadBufferOptions {
+        page: 1,
+        dpi: 144.0,
+        ..ops::PdfloadBufferOptions::default()
+    };
+    let image = ops::pdfload_buffer_with_opts(&buffer, &options).unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (288, 144));
+
+    let options = ops::PdfloadBufferOptions {
+        scale: 0.5,
+        ..ops::PdfloadBufferOptions::default()
+    };
+    let image = ops::pdfload_buffer_with_opts(&buffer, &options).unwrap();
+    assert_eq!(image.get_width(), 36);
+
+    let image = ops::pdfload("images/test.pdf").unwrap();
+    assert_eq!(image.get_n_pages(), 2);
+}
+
+#[test]
+fn test_render_pdf() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.pdf").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    if !action::ImageFormat::Pdf.can_load() {
+        println!("libvips was built without PDF support, skipping");
+        return
+    }
+
+    let result = action::render_pdf(&buffer, 1, 72.0, action::ImageFormat::Png).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!((image.get_width(), image.get_height()), (144, 72));
+
+    let mut png = Vec::new();
+    File::open("images/test.png").unwrap().read_to_end(&mut png).unwrap();
+    assert!(action::render_pdf(&png, 0, 72.0, action::ImageFormat::Png).is_err());
+}
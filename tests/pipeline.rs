@@ -0,0 +1,53 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp};
+
+#[test]
+fn test_pipeline() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let watermark_options = action::WatermarkOptions {
+        gravity: action::Gravity::SouthEast,
+        x: 10,
+        y: 10,
+        opacity: 0.5,
+        ..action::WatermarkOptions::default()
+    };
+    let result = action::Pipeline::new()
+        .crop(800, 800, action::Gravity::Centre)
+        .resize(400, 0)
+        .rotate(90.0, &action::RotateOptions::default())
+        .watermark_text("pipeline", 72, "#FFFFFF", &watermark_options)
+        .sharpen(&ops::SharpenOptions::default())
+        .strip(action::StripPolicy::KeepIcc)
+        .format(action::ImageFormat::Png)
+        .run(&buffer)
+        .unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.ttype, "png");
+    assert_eq!(metadata.width, 400);
+    assert_eq!(metadata.height, 400);
+
+    let mut file = File::create("images/pipeline_test.png").unwrap();
+    file.write(&result);
+}
+
+#[test]
+fn test_pipeline_colourspace() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let result = action::Pipeline::new()
+        .colourspace(ops::Interpretation::BW)
+        .run(&buffer)
+        .unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.ttype, "jpeg");
+    assert_eq!(metadata.space, "bw");
+    assert_eq!(metadata.width, 1200);
+}
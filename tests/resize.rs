@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::prelude::*;
-use libvips::{action, VipsApp};
+use libvips::{action, ops, VipsApp};
 
 #[test]
 fn test_resize() {
@@ -28,3 +28,81 @@ fn test_resize() {
         file.write(&result);
     }
 }
+
+#[test]
+fn test_resize_fit() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    // test.jpeg is 1200x800
+    let cases = vec![
+        (action::Fit::Inside, false, 300, 300, 300, 200),
+        (action::Fit::Outside, false, 300, 300, 450, 300),
+        (action::Fit::Cover, false, 300, 300, 300, 300),
+        (action::Fit::Contain, false, 300, 300, 300, 300),
+        (action::Fit::Fill, false, 300, 100, 300, 100),
+        (action::Fit::Inside, true, 2000, 2000, 1200, 800),
+        (action::Fit::Cover, true, 2000, 500, 1200, 500),
+        (action::Fit::Contain, true, 2000, 2000, 2000, 2000),
+    ];
+    for (fit, without_enlargement, width, height, expected_width, expected_height) in cases {
+        let resize_options = action::ResizeOptions {
+            fit,
+            without_enlargement,
+            background: vec![255.0],
+            ..action::ResizeOptions::default()
+        };
+        let result = action::resize_with_opts(&buffer, width, height, &resize_options, &action::ActionOptions::default()).unwrap();
+        let metadata = action::get_metadata(&result).unwrap();
+        assert_eq!((metadata.width, metadata.height), (expected_width, expected_height), "{:?}", fit);
+    }
+}
+
+#[test]
+fn test_resize_targets() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let result = action::resize_scale(&buffer, 0.5, ops::Kernel::Nearest).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (600, 400));
+
+    let result = action::resize_longest_edge(&buffer, 600).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (600, 400));
+
+    let result = action::resize_longest_edge(&buffer, 2000).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (1200, 800));
+
+    let result = action::resize_max_pixels(&buffer, 0.24).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (600, 400));
+
+    let result = action::resize_max_pixels(&buffer, 12.0).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (1200, 800));
+
+    assert!(action::resize_scale(&buffer, 0.0, ops::Kernel::Lanczos3).is_err());
+    assert!(action::resize_max_pixels(&buffer, -1.0).is_err());
+
+    // stored as 1200 x 800 with orientation 6, so upright it is 800 x 1200
+    let mut f = File::open("images/test_orientation_6.jpeg").unwrap();
+    let mut rotated: Vec<u8> = Vec::new();
+    f.read_to_end(&mut rotated).unwrap();
+    let result = action::resize_max_pixels(&rotated, 0.24).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (400, 600));
+
+    let unrotated = action::ActionOptions {
+        auto_orient: false,
+        ..action::ActionOptions::default()
+    };
+    let result = action::resize_max_pixels_with_opts(&rotated, 0.24, &unrotated).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!((metadata.width, metadata.height), (600, 400));
+}
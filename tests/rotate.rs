@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::prelude::*;
-use libvips::{action, VipsApp};
+use libvips::{action, VipsApp, VipsImage};
 
 #[test]
 fn test_rotate() {
@@ -22,3 +22,33 @@ fn test_rotate() {
         file.write(&result);
     }
 }
+
+#[test]
+fn test_rotate_arbitrary() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.png").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer).unwrap();
+    let input = VipsImage::new_from_buffer(&buffer, "").unwrap();
+
+    let options = action::RotateOptions {
+        background: vec![255.0],
+        interpolate: String::from("bilinear"),
+    };
+    let result =
+        action::rotate_with_opts(&buffer, 45.0, &options, &action::ActionOptions::default()).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert!(image.get_width() > input.get_width());
+
+    let result = action::rotate(&buffer, -90).unwrap();
+    let image = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert_eq!(image.get_width(), input.get_height());
+
+    let result = action::rotate_with_opts(
+        &buffer,
+        std::f64::NAN,
+        &action::RotateOptions::default(),
+        &action::ActionOptions::default(),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::{action, ops, VipsApp, VipsImage};
+
+#[test]
+fn test_strip_metadata() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let images = vec!["test.jpeg", "test.png", "test.webp", "test.tiff"];
+    let policies = vec![
+        action::StripPolicy::KeepIcc,
+        action::StripPolicy::KeepOrientationAndIcc,
+        action::StripPolicy::KeepCopyright,
+        action::StripPolicy::StripAll,
+    ];
+    for i in images.iter() {
+        let mut f = File::open(format!("images/{}", i)).unwrap();
+        let mut buffer: Vec<u8> = Vec::new();
+        f.read_to_end(&mut buffer).unwrap();
+
+        for policy in policies.iter() {
+            let result = action::strip_metadata(&buffer, *policy).unwrap();
+            assert_no_gps(&result);
+        }
+    }
+}
+
+#[test]
+fn test_strip_metadata_removes_gps() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let image = VipsImage::new_from_file("images/test.jpeg").unwrap();
+    let mut image = ops::copy(&image).unwrap();
+    image.set_string("exif-ifd0-Copyright", "(c) libvips").unwrap();
+    image.set_string("exif-ifd3-GPSLatitudeRef", "N").unwrap();
+    image.set_string("exif-ifd3-GPSLatitude", "52/1 31/1 0/1").unwrap();
+    let buffer = image.image_write_to_buffer(".jpeg").unwrap();
+
+    let result = action::strip_metadata(&buffer, action::StripPolicy::KeepCopyright).unwrap();
+    assert_no_gps(&result);
+    let stripped = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert!(stripped
+        .get_string("exif-ifd0-Copyright")
+        .unwrap()
+        .starts_with("(c) libvips"));
+
+    let result = action::strip_metadata(&buffer, action::StripPolicy::StripAll).unwrap();
+    let stripped = VipsImage::new_from_buffer(&result, "").unwrap();
+    assert!(stripped.get_fields().iter().all(|f| !f.starts_with("exif-")));
+
+    // HEIF support is optional in libvips builds
+    if let Ok(heif) = image.image_write_to_buffer(".heif") {
+        let result = action::strip_metadata(&heif, action::StripPolicy::KeepIcc).unwrap();
+        assert_no_gps(&result);
+    }
+}
+
+fn assert_no_gps(buffer: &[u8]) {
+    let image = VipsImage::new_from_buffer(buffer, "").unwrap();
+    for field in image.get_fields() {
+        assert!(!field.contains("GPS"), "found {} after stripping", field);
+    }
+}
@@ -0,0 +1,58 @@
+use libvips::{ops, VipsApp};
+
+#[test]
+fn test_text_with_style() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let text_style = ops::TextStyle {
+        font: String::from("sans 12"),
+        width: 200,
+        align: ops::Align::Centre,
+        justify: true,
+        dpi: 150,
+        ..ops::TextStyle::default()
+    };
+    let text = "A caption long enough to wrap onto a few lines <with> markup & quotes";
+    let mask = ops::text_with_style(text, &text_style).unwrap();
+    assert_eq!(mask.get_bands(), 1);
+    assert!(mask.get_width() <= 200);
+
+    let rgba_style = ops::TextStyle {
+        rgba: true,
+        color: String::from("#FF1493"),
+        ..text_style
+    };
+    let image = ops::text_with_style(text, &rgba_style).unwrap();
+    assert_eq!(image.get_bands(), 4);
+    assert_eq!(image.get_width(), mask.get_width());
+
+    // nothing set but the text: no font, font file or size is passed on
+    let plain = ops::text_with_style(text, &ops::TextStyle::default()).unwrap();
+    assert_eq!(plain.get_bands(), 1);
+
+    let boxed = ops::TextStyle {
+        width: 300,
+        height: 100,
+        ..ops::TextStyle::default()
+    };
+    let image = ops::text_with_style(text, &boxed).unwrap();
+    assert!(image.get_width() <= 300 && image.get_height() <= 100);
+
+    let image = ops::text_with_opts1(text, 150, "#FF1493").unwrap();
+    assert_eq!(image.get_bands(), 4);
+    assert_eq!(ops::getpoint(&image, 0, 0).unwrap()[..3], [255.0, 20.0, 147.0]);
+
+    let invalid = ops::TextStyle {
+        height: 100,
+        ..ops::TextStyle::default()
+    };
+    assert!(ops::text_with_style(text, &invalid).is_err());
+}
+
+#[test]
+fn test_escape_markup() {
+    assert_eq!(ops::escape_markup("plain"), "plain");
+    assert_eq!(
+        ops::escape_markup("<b>Tom & \"Jerry's\"</b>"),
+        "&lt;b&gt;Tom &amp; &quot;Jerry&apos;s&quot;&lt;/b&gt;"
+    );
+}
@@ -0,0 +1,118 @@
+use std::fs::File;
+use std::io::prelude::*;
+use libvips::action::{self, Fit, Gravity, ImageFormat};
+use libvips::transform::{SpecError, TransformSpec};
+use libvips::VipsApp;
+
+#[test]
+fn test_parse_query() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let spec = TransformSpec::from_query("?w=300&h=200&fit=cover&g=ne&fmt=webp&q=80&strip").unwrap();
+    assert_eq!(spec.width, Some(300));
+    assert_eq!(spec.height, Some(200));
+    assert_eq!(spec.fit, Fit::Cover);
+    assert_eq!(spec.gravity, Gravity::NorthEast);
+    assert_eq!(spec.format, Some(ImageFormat::Webp));
+    assert_eq!(spec.quality, Some(80));
+    assert_eq!(spec.strip, true);
+
+    let spec = TransformSpec::from_query("width=640&format=JPG&gravity=smart&rot=90").unwrap();
+    assert_eq!(spec.width, Some(640));
+    assert_eq!(spec.height, None);
+    assert_eq!(spec.fit, Fit::Inside);
+    assert_eq!(spec.gravity, Gravity::Entropy);
+    assert_eq!(spec.format, Some(ImageFormat::Jpeg));
+    assert_eq!(spec.rotate, Some(90));
+
+    assert_eq!(TransformSpec::from_query("").unwrap(), TransformSpec::default());
+    assert_eq!(TransformSpec::from_query("g=%6Eorth").unwrap().gravity, Gravity::North);
+}
+
+#[test]
+fn test_parse_query_errors() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    assert_eq!(
+        TransformSpec::from_query("w=300&blur=5"),
+        Err(SpecError::UnknownKey(String::from("blur")))
+    );
+    assert_eq!(
+        TransformSpec::from_query("w=300&width=400"),
+        Err(SpecError::DuplicateKey(String::from("width")))
+    );
+    assert_eq!(
+        TransformSpec::from_query("w=abc"),
+        Err(SpecError::InvalidValue {
+            key: String::from("w"),
+            value: String::from("abc")
+        })
+    );
+    assert_eq!(
+        TransformSpec::from_query("q=101"),
+        Err(SpecError::OutOfRange {
+            key: String::from("q"),
+            value: 101,
+            min: 1,
+            max: 100
+        })
+    );
+    assert_eq!(
+        TransformSpec::from_query("w=0"),
+        Err(SpecError::OutOfRange {
+            key: String::from("w"),
+            value: 0,
+            min: 1,
+            max: 10000
+        })
+    );
+    assert!(TransformSpec::from_query("rot=45").is_err());
+    assert!(TransformSpec::from_query("fmt=exe").is_err());
+    for format in &["pdf", "svg", "bmp", "ico", "psd"] {
+        assert_eq!(
+            TransformSpec::from_query(&format!("fmt={}", format)),
+            Err(SpecError::InvalidValue {
+                key: String::from("fmt"),
+                value: format.to_string()
+            })
+        );
+    }
+    // not an escape, so the `%` stays and the `+` is a space
+    assert_eq!(
+        TransformSpec::from_query("g=%+1"),
+        Err(SpecError::InvalidValue {
+            key: String::from("g"),
+            value: String::from("% 1")
+        })
+    );
+    assert_eq!(
+        TransformSpec::from_query("w=300&fit=fill"),
+        Err(SpecError::MissingDimension(Fit::Fill))
+    );
+}
+
+#[test]
+fn test_apply() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let spec = TransformSpec::from_query("w=300&h=200&fit=cover&fmt=webp&q=80").unwrap();
+    let result = spec.apply(&buffer).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.ttype, "webp");
+    assert_eq!(metadata.width, 300);
+    assert_eq!(metadata.height, 200);
+
+    let spec = TransformSpec::from_query("w=300").unwrap();
+    let result = spec.apply(&buffer).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.ttype, "jpeg");
+    assert_eq!(metadata.width, 300);
+    assert_eq!(metadata.height, 200);
+
+    let spec = TransformSpec::from_query("w=100&h=100&fit=fill").unwrap();
+    let result = spec.apply(&buffer).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.width, 100);
+    assert_eq!(metadata.height, 100);
+}
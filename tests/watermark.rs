@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::prelude::*;
-use libvips::{action, VipsApp};
+use libvips::{action, ops, VipsApp, VipsImage};
 
 #[test]
 fn test_watermark() {
@@ -35,7 +35,7 @@ fn test_watermark() {
             return;
         }
     };
-    let result = match action::watermark_image(&buffer, &overlay, 0, 0, 0) {
+    let result = match action::watermark_image(&buffer, &overlay, 10, 10, 50) {
         Ok(_result) => _result,
         Err(e) => {
             println!("failed to watermark image, err: {:?}", e);
@@ -44,3 +44,125 @@ fn test_watermark() {
     };
     file.write(&result);
 }
+
+#[test]
+fn test_watermark_position() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let overlay = action::resize(&buffer, 100, 100).unwrap();
+    let watermark_options = action::WatermarkOptions {
+        gravity: action::Gravity::SouthEast,
+        x: 20,
+        y: 20,
+        scale: 0.25,
+        opacity: 0.5,
+        mode: action::WatermarkMode::Single,
+    };
+    let result = action::watermark_image_with_opts(&buffer, &overlay, &watermark_options, &action::ActionOptions::default()).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.width, 1200);
+    assert_eq!(metadata.height, 800);
+    assert_eq!(metadata.alpha, false);
+
+    let result = action::watermark_text_with_opts(&buffer, "watermark", 72, "#F00", &watermark_options, &action::ActionOptions::default()).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.width, 1200);
+    assert_eq!(metadata.alpha, false);
+
+    let invalid = action::WatermarkOptions {
+        opacity: 1.5,
+        ..action::WatermarkOptions::default()
+    };
+    assert!(action::watermark_image_with_opts(&buffer, &overlay, &invalid, &action::ActionOptions::default()).is_err());
+}
+
+// a black base and a white overlay as PNG, so every pixel reads back exactly
+fn solid_png(width: i32, height: i32, white: bool) -> Vec<u8> {
+    let image = ops::black(width, height).unwrap();
+    let image = if white { ops::invert(&image).unwrap() } else { image };
+    image.image_write_to_buffer(".png").unwrap()
+}
+
+fn pixel(buf: &[u8], x: i32, y: i32) -> f64 {
+    let image = VipsImage::new_from_buffer(buf, "").unwrap();
+    ops::getpoint(&image, x, y).unwrap()[0]
+}
+
+#[test]
+fn test_watermark_pixels() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let base = solid_png(400, 300, false);
+    let overlay = solid_png(40, 40, true);
+
+    // south-east, 20 pixels in from both edges: the overlay covers 340..380 x 240..280
+    let watermark_options = action::WatermarkOptions {
+        gravity: action::Gravity::SouthEast,
+        x: 20,
+        y: 20,
+        ..action::WatermarkOptions::default()
+    };
+    let result = action::watermark_image_with_opts(&base, &overlay, &watermark_options, &action::ActionOptions::default()).unwrap();
+    assert_eq!(pixel(&result, 360, 260), 255.0);
+    assert_eq!(pixel(&result, 395, 295), 0.0);
+    assert_eq!(pixel(&result, 10, 10), 0.0);
+
+    // opacity 0 leaves the base as it is, 100 covers it
+    let hidden = action::watermark_image(&base, &overlay, 10, 10, 0).unwrap();
+    let opaque = action::watermark_image(&base, &overlay, 10, 10, 100).unwrap();
+    let half = action::watermark_image(&base, &overlay, 10, 10, 50).unwrap();
+    assert_eq!(pixel(&hidden, 30, 30), pixel(&base, 30, 30));
+    assert_eq!(pixel(&opaque, 30, 30), 255.0);
+    assert!((pixel(&half, 30, 30) - 127.5).abs() <= 1.0);
+    assert_eq!(pixel(&opaque, 60, 60), 0.0);
+}
+
+#[test]
+fn test_watermark_tiled_pixels() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let base = solid_png(400, 300, false);
+    let overlay = solid_png(40, 40, true);
+
+    // each 80 x 80 tile holds the overlay at 20..60, with the spacing split around it
+    let watermark_options = action::WatermarkOptions {
+        mode: action::WatermarkMode::Tiled { angle: 0.0, spacing: 40 },
+        ..action::WatermarkOptions::default()
+    };
+    let result = action::watermark_image_with_opts(&base, &overlay, &watermark_options, &action::ActionOptions::default()).unwrap();
+    for (x, y) in [(40, 40), (120, 40), (40, 120), (360, 280)].iter() {
+        assert_eq!(pixel(&result, *x, *y), 255.0, "({}, {})", x, y);
+    }
+    for (x, y) in [(10, 10), (70, 70), (150, 150)].iter() {
+        assert_eq!(pixel(&result, *x, *y), 0.0, "({}, {})", x, y);
+    }
+
+    let hidden = action::WatermarkOptions {
+        opacity: 0.0,
+        ..watermark_options
+    };
+    let result = action::watermark_image_with_opts(&base, &overlay, &hidden, &action::ActionOptions::default()).unwrap();
+    assert_eq!(pixel(&result, 40, 40), 0.0);
+}
+
+#[test]
+fn test_watermark_tiled() {
+    let _app = VipsApp::new("Test Libvips", false).expect("Cannot initialize libvips");
+    let mut f = File::open("images/test.jpeg").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    f.read_to_end(&mut buffer);
+
+    let watermark_options = action::WatermarkOptions {
+        opacity: 0.3,
+        mode: action::WatermarkMode::Tiled { angle: -30.0, spacing: 80 },
+        ..action::WatermarkOptions::default()
+    };
+    let result = action::watermark_text_with_opts(&buffer, "preview", 72, "#FFFFFF", &watermark_options, &action::ActionOptions::default()).unwrap();
+    let metadata = action::get_metadata(&result).unwrap();
+    assert_eq!(metadata.width, 1200);
+    assert_eq!(metadata.height, 800);
+
+    let mut file = File::create("images/watermark_tiled_test.jpeg").unwrap();
+    file.write(&result);
+}
@@ -1,2 +1,18 @@
 // (c) Copyright 2019-2020 OLX
-#include <vips/vips.h>
\ No newline at end of file
+#include <vips/vips.h>
+
+/* JPEG XL arrived in libvips 8.11, after the headers these bindings were
+ * generated from, so the declarations are repeated here for bindgen.
+ */
+int vips_jxlload(const char *filename, VipsImage **out, ...)
+	G_GNUC_NULL_TERMINATED;
+int vips_jxlload_buffer(void *buf, size_t len, VipsImage **out, ...)
+	G_GNUC_NULL_TERMINATED;
+int vips_jxlload_source(VipsSource *source, VipsImage **out, ...)
+	G_GNUC_NULL_TERMINATED;
+int vips_jxlsave(VipsImage *in, const char *filename, ...)
+	G_GNUC_NULL_TERMINATED;
+int vips_jxlsave_buffer(VipsImage *in, void **buf, size_t *len, ...)
+	G_GNUC_NULL_TERMINATED;
+int vips_jxlsave_target(VipsImage *in, VipsTarget *target, ...)
+	G_GNUC_NULL_TERMINATED;